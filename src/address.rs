@@ -0,0 +1,225 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+static STREET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?P<number>\d+[a-z]?(?:-\d+)?)\s+(?P<name>.+?)(?:\s*,?\s+(?P<unit>(?:apt|apartment|unit|ste|suite|lot|bldg|#)\.?\s*#?\s*[a-z0-9-]+))?$",
+    )
+    .unwrap()
+});
+
+static UNIT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(?:apt|apartment|unit|ste|suite|lot|bldg|#)\.?\s*#?\s*[a-z0-9-]+$").unwrap()
+});
+
+// US zip codes and Canadian postal codes.
+static LOCALITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?P<city>[^,]+),\s*(?P<state>[a-z][a-z .]*?)(?:\s+(?P<postal>\d{5}(?:-\d{4})?|[a-z]\d[a-z][ -]?\d[a-z]\d))?$",
+    )
+    .unwrap()
+});
+
+// Localities without a state, like `London SW1A 2AA`, `Lima 15001` or `75008 Paris`. Only tried
+// once a country has been found, since North American ones always have the comma.
+static INTERNATIONAL_LOCALITY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?i)^(?:(?P<city>[^\d,]+?)\s+(?P<postal>[a-z0-9]*\d[a-z0-9]*(?:[ -][a-z0-9]+)?)|(?P<postal_first>\d[\d -]*\d)\s+(?P<city_last>[^\d,]+))$",
+    )
+    .unwrap()
+});
+
+static COUNTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^[a-z][a-z .'-]+$").unwrap());
+
+/// The components of an LCR address, parsed out of its display lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedAddress {
+    pub street_number: Option<String>,
+    pub street_name: Option<String>,
+    pub unit: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+
+    /// Lines that didn't look like any component we know how to parse.
+    pub unparsed_lines: Vec<String>,
+}
+
+impl ParsedAddress {
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Self {
+        let mut parsed = Self::default();
+        let mut lines: Vec<_> = lines
+            .iter()
+            .map(|l| l.as_ref().trim())
+            .filter(|l| !l.is_empty())
+            .collect();
+
+        // The country is always last when there is one, whatever the layout of the lines above.
+        if lines.len() > 1
+            && let Some(last) = lines.last()
+            && COUNTRY.is_match(last)
+        {
+            parsed.country = Some(last.to_string());
+            lines.pop();
+        }
+
+        for line in lines {
+            if parsed.street_name.is_none()
+                && parsed.city.is_none()
+                && let Some(caps) = STREET.captures(line)
+            {
+                parsed.street_number = caps.name("number").map(|m| m.as_str().to_string());
+                parsed.street_name = caps.name("name").map(|m| m.as_str().to_string());
+                parsed.unit = caps.name("unit").map(|m| m.as_str().to_string());
+                continue;
+            }
+
+            if parsed.unit.is_none() && parsed.city.is_none() && UNIT.is_match(line) {
+                parsed.unit = Some(line.to_string());
+                continue;
+            }
+
+            if parsed.city.is_none() {
+                if let Some(caps) = LOCALITY.captures(line) {
+                    parsed.city = caps.name("city").map(|m| m.as_str().trim().to_string());
+                    parsed.state = caps.name("state").map(|m| m.as_str().trim().to_string());
+                    parsed.postal_code = caps.name("postal").map(|m| m.as_str().to_uppercase());
+                    continue;
+                }
+
+                if parsed.country.is_some()
+                    && let Some(caps) = INTERNATIONAL_LOCALITY.captures(line)
+                {
+                    parsed.city = caps
+                        .name("city")
+                        .or_else(|| caps.name("city_last"))
+                        .map(|m| m.as_str().trim().to_string());
+                    parsed.postal_code = caps
+                        .name("postal")
+                        .or_else(|| caps.name("postal_first"))
+                        .map(|m| m.as_str().to_uppercase());
+                    continue;
+                }
+            }
+
+            parsed.unparsed_lines.push(line.to_string());
+        }
+
+        parsed
+    }
+
+    /// Whether there's enough here to mail something: a street, city, state and postal code.
+    /// Country is optional since LCR leaves it off for addresses in the unit's own country.
+    pub fn is_complete(&self) -> bool {
+        self.street_number.is_some()
+            && self.street_name.is_some()
+            && self.city.is_some()
+            && self.state.is_some()
+            && self.postal_code.is_some()
+    }
+
+    /// Key for sorting addresses by street, then house number, then unit.
+    pub fn street_sort_key(&self) -> (String, u64, String) {
+        let number = self
+            .street_number
+            .as_deref()
+            .map(|n| {
+                n.chars()
+                    .take_while(|c| c.is_ascii_digit())
+                    .collect::<String>()
+            })
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(u64::MAX);
+
+        (
+            self.street_name.as_deref().unwrap_or("").to_lowercase(),
+            number,
+            self.unit.as_deref().unwrap_or("").to_lowercase(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_us_address() {
+        let parsed = ParsedAddress::parse(&["1234 N 500 E Apt 12", "Provo, Utah 84604-1234"]);
+        assert_eq!(parsed.street_number.as_deref(), Some("1234"));
+        assert_eq!(parsed.street_name.as_deref(), Some("N 500 E"));
+        assert_eq!(parsed.unit.as_deref(), Some("Apt 12"));
+        assert_eq!(parsed.city.as_deref(), Some("Provo"));
+        assert_eq!(parsed.state.as_deref(), Some("Utah"));
+        assert_eq!(parsed.postal_code.as_deref(), Some("84604-1234"));
+        assert_eq!(parsed.country, None);
+        assert!(parsed.unparsed_lines.is_empty());
+        assert!(parsed.is_complete());
+    }
+
+    #[test]
+    fn test_parse_separate_unit_and_country() {
+        let parsed =
+            ParsedAddress::parse(&["45 Maple Street", "Unit 3", "Calgary, AB t2p 1j9", "Canada"]);
+        assert_eq!(parsed.street_name.as_deref(), Some("Maple Street"));
+        assert_eq!(parsed.unit.as_deref(), Some("Unit 3"));
+        assert_eq!(parsed.state.as_deref(), Some("AB"));
+        assert_eq!(parsed.postal_code.as_deref(), Some("T2P 1J9"));
+        assert_eq!(parsed.country.as_deref(), Some("Canada"));
+        assert!(parsed.is_complete());
+    }
+
+    #[test]
+    fn test_parse_international_addresses() {
+        let uk = ParsedAddress::parse(&["10 Downing Street", "London SW1A 2AA", "United Kingdom"]);
+        assert_eq!(uk.street_name.as_deref(), Some("Downing Street"));
+        assert_eq!(uk.city.as_deref(), Some("London"));
+        assert_eq!(uk.postal_code.as_deref(), Some("SW1A 2AA"));
+        assert_eq!(uk.country.as_deref(), Some("United Kingdom"));
+        assert!(uk.unparsed_lines.is_empty());
+
+        let peru = ParsedAddress::parse(&["Calle 5 #12", "Lima 15001", "Peru"]);
+        assert_eq!(peru.city.as_deref(), Some("Lima"));
+        assert_eq!(peru.postal_code.as_deref(), Some("15001"));
+        assert_eq!(peru.country.as_deref(), Some("Peru"));
+        assert_eq!(peru.unparsed_lines, vec!["Calle 5 #12".to_string()]);
+
+        let france = ParsedAddress::parse(&["8 Rue de Rivoli", "75004 Paris", "France"]);
+        assert_eq!(france.city.as_deref(), Some("Paris"));
+        assert_eq!(france.postal_code.as_deref(), Some("75004"));
+    }
+
+    #[test]
+    fn test_parse_incomplete_address() {
+        let parsed = ParsedAddress::parse(&["PO Box 12", "Provo, Utah"]);
+        assert_eq!(parsed.street_name, None);
+        assert_eq!(parsed.city.as_deref(), Some("Provo"));
+        assert_eq!(parsed.postal_code, None);
+        assert_eq!(parsed.unparsed_lines, vec!["PO Box 12".to_string()]);
+        assert!(!parsed.is_complete());
+    }
+
+    #[test]
+    fn test_street_sort_key() {
+        let a = ParsedAddress::parse(&["90 Elm St", "Provo, UT 84604"]);
+        let b = ParsedAddress::parse(&["100 Elm St", "Provo, UT 84604"]);
+        let c = ParsedAddress::parse(&["5 Ash St", "Provo, UT 84604"]);
+        let mut keys = vec![
+            b.street_sort_key(),
+            c.street_sort_key(),
+            a.street_sort_key(),
+        ];
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                c.street_sort_key(),
+                a.street_sort_key(),
+                b.street_sort_key()
+            ]
+        );
+    }
+}
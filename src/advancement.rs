@@ -0,0 +1,169 @@
+//! Upcoming age milestones computed from birth dates in the member list: children turning 8,
+//! and youth advancing in January of the year they turn 12, 14 or 16.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::{Date, Month};
+
+use crate::birthdays::in_year;
+use crate::data::{MemberListPerson, Sex};
+
+// Ages youth advance at, in January of the year they reach them.
+const ADVANCEMENT_AGES: [i32; 3] = [12, 14, 16];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MilestoneKind {
+    TurnsEight,
+    YouthAdvancement,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Milestone<'a> {
+    /// Year and month of the milestone, like `2026-01`.
+    pub month: String,
+    pub date: String,
+    pub name: &'a str,
+    pub legacy_cmis_id: u64,
+    pub kind: MilestoneKind,
+    pub age: i32,
+    pub description: String,
+}
+
+fn advancement_description(age: i32, sex: Option<Sex>) -> String {
+    match (age, sex) {
+        (12, Some(Sex::Male)) => "Leaves Primary; deacon ordination; limited-use recommend",
+        (12, _) => "Leaves Primary; limited-use recommend",
+        (14, Some(Sex::Male)) => "Teacher ordination",
+        (16, Some(Sex::Male)) => "Priest ordination",
+        _ => "Advances to the next class",
+    }
+    .to_string()
+}
+
+fn sex(member: &MemberListPerson) -> Option<Sex> {
+    [Sex::Male, Sex::Female]
+        .into_iter()
+        .find(|s| s.matches(member))
+}
+
+/// Last day of the month `months` after `date`'s month.
+fn end_of_month_after(date: Date, months: u8) -> Date {
+    let index = date.year() * 12 + i32::from(u8::from(date.month())) - 1 + i32::from(months);
+    let year = index.div_euclid(12);
+    let month = Month::try_from((index.rem_euclid(12) + 1) as u8).expect("Month is 1 through 12");
+    Date::from_calendar_date(year, month, month.length(year)).expect("Last day of the month")
+}
+
+/// Milestones from `today` through the end of the month `months` from now, soonest first.
+/// Members without a birth date are skipped.
+pub fn upcoming_milestones(
+    members: &[MemberListPerson],
+    today: Date,
+    months: u8,
+) -> Vec<Milestone<'_>> {
+    let end = end_of_month_after(today, months);
+    let mut milestones = Vec::new();
+
+    for member in members {
+        let Some(birth_date) = member.birth_date() else {
+            continue;
+        };
+        let mut push = |date: Date, kind, age, description| {
+            if (today..=end).contains(&date) {
+                milestones.push(Milestone {
+                    month: format!("{}-{:02}", date.year(), u8::from(date.month())),
+                    date: date.to_string(),
+                    name: &member.name_list_preferred_local,
+                    legacy_cmis_id: member.legacy_cmis_id,
+                    kind,
+                    age,
+                    description,
+                });
+            }
+        };
+
+        let eighth = in_year(birth_date, birth_date.year() + 8);
+        push(
+            eighth,
+            MilestoneKind::TurnsEight,
+            8,
+            "Turns 8; eligible for baptism".to_string(),
+        );
+
+        for age in ADVANCEMENT_AGES {
+            let year = birth_date.year() + age;
+            let january = Date::from_calendar_date(year, Month::January, 1)
+                .expect("January 1st exists every year");
+            push(
+                january,
+                MilestoneKind::YouthAdvancement,
+                age,
+                advancement_description(age, sex(member)),
+            );
+        }
+    }
+
+    milestones.sort_by(|a, b| (&a.date, a.name).cmp(&(&b.date, b.name)));
+    milestones
+}
+
+pub fn group_by_month<'a, 'b>(
+    milestones: &'b [Milestone<'a>],
+) -> BTreeMap<&'b str, Vec<&'b Milestone<'a>>> {
+    let mut by_month: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for milestone in milestones {
+        by_month
+            .entry(milestone.month.as_str())
+            .or_default()
+            .push(milestone);
+    }
+    by_month
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn member(id: u64, sex: &str, birth_date: &str) -> MemberListPerson {
+        let mut member = MemberListPerson::test(id, 10, sex, "a");
+        member.birth_date = Some(birth_date.to_string());
+        member
+    }
+
+    #[test]
+    fn test_upcoming_milestones() {
+        let members = vec![
+            member(1, "M", "20171203"),
+            member(2, "F", "20141105"),
+            member(3, "M", "20120620"),
+            member(4, "F", "20171001"),
+            MemberListPerson::test(5, 7, "M", "b"),
+        ];
+
+        let milestones = upcoming_milestones(&members, date!(2025 - 10 - 18), 3);
+        let found: Vec<_> = milestones
+            .iter()
+            .map(|m| (m.legacy_cmis_id, m.date.as_str(), m.age))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, "2025-12-03", 8),
+                (2, "2026-01-01", 12),
+                (3, "2026-01-01", 14),
+            ]
+        );
+        assert_eq!(milestones[2].description, "Teacher ordination");
+
+        let by_month = group_by_month(&milestones);
+        assert_eq!(by_month["2026-01"].len(), 2);
+        assert_eq!(
+            end_of_month_after(date!(2025 - 11 - 30), 2),
+            date!(2026 - 01 - 31)
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! Importing class and quorum attendance from spreadsheets and roll-call lists, and working out
+//! what needs to change in LCR.
+
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use time::{Date, Weekday};
+
+use crate::data::{AttendanceUpdate, Attendee, ClassAttendance, parse_date};
+use crate::error::Error;
+
+/// One person present or absent on one Sunday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttendanceMark {
+    pub legacy_cmis_id: u64,
+    pub date: Date,
+    pub attended: bool,
+}
+
+/// One row per attendee per Sunday, for flat exports like CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceRow<'a> {
+    pub name: &'a str,
+    pub legacy_cmis_id: u64,
+    pub date: &'a str,
+    pub attended: bool,
+}
+
+pub fn rows(attendance: &ClassAttendance) -> Vec<AttendanceRow<'_>> {
+    attendance
+        .attendees
+        .iter()
+        .flat_map(|a| {
+            a.entries.iter().map(move |e| AttendanceRow {
+                name: &a.name,
+                legacy_cmis_id: a.legacy_cmis_id,
+                date: &e.date,
+                attended: e.attended,
+            })
+        })
+        .collect()
+}
+
+/// Lowercases a name and puts `Family, Given` names in `given family` order so either form
+/// matches.
+fn normalize_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let name = match name.split_once(',') {
+        Some((family, given)) => format!("{} {}", given.trim(), family.trim()),
+        None => name,
+    };
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn find_attendee<'a>(attendance: &'a ClassAttendance, name: &str) -> Result<&'a Attendee, Error> {
+    let normalized = normalize_name(name);
+    let mut matches = attendance
+        .attendees
+        .iter()
+        .filter(|a| normalize_name(&a.name) == normalized);
+    match (matches.next(), matches.next()) {
+        (Some(attendee), None) => Ok(attendee),
+        (None, _) => Err(Error::InvalidInput(format!(
+            "Nobody named {} is in this class",
+            name.trim()
+        ))),
+        (Some(_), Some(_)) => Err(Error::InvalidInput(format!(
+            "More than one person named {} is in this class, use legacyCmisId instead",
+            name.trim()
+        ))),
+    }
+}
+
+fn check_sunday(date: Date) -> Result<(), Error> {
+    if date.weekday() == Weekday::Sunday {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput(format!(
+            "{} is a {}, not a Sunday",
+            date,
+            date.weekday()
+        )))
+    }
+}
+
+fn parse_attended(value: &str) -> Result<bool, Error> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "x" | "1" | "present" => Ok(true),
+        "false" | "no" | "n" | "" | "0" | "absent" => Ok(false),
+        _ => Err(Error::InvalidInput(format!(
+            "Not a present/absent value: {}",
+            value
+        ))),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CsvRow {
+    date: String,
+    legacy_cmis_id: Option<u64>,
+    name: Option<String>,
+    attended: String,
+}
+
+/// Reads a CSV with `date` and `attended` columns, plus `legacyCmisId` or `name` to say who.
+///
+/// `attendance` is what LCR has for `start` through `end`, usually the range from
+/// [`csv_date_range`].
+///
+/// # Errors
+/// Malformed CSV, dates that don't parse or aren't Sundays from `start` through `end`, or people
+/// not in the class.
+pub fn read_csv<R: Read>(
+    reader: R,
+    attendance: &ClassAttendance,
+    start: Date,
+    end: Date,
+) -> Result<Vec<AttendanceMark>, Error> {
+    let mut marks = Vec::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: CsvRow = row?;
+        let date = parse_date(&row.date)
+            .ok_or_else(|| Error::InvalidInput(format!("Not a date: {}", row.date)))?;
+        check_sunday(date)?;
+        if !(start..=end).contains(&date) {
+            return Err(Error::InvalidInput(format!(
+                "{} is outside the fetched weeks, {} through {}",
+                date, start, end
+            )));
+        }
+        let legacy_cmis_id = match (row.legacy_cmis_id, &row.name) {
+            (Some(id), _) if attendance.attendees.iter().any(|a| a.legacy_cmis_id == id) => id,
+            (Some(id), _) => {
+                return Err(Error::InvalidInput(format!(
+                    "Nobody with legacyCmisId {} is in this class",
+                    id
+                )));
+            }
+            (None, Some(name)) => find_attendee(attendance, name)?.legacy_cmis_id,
+            (None, None) => {
+                return Err(Error::InvalidInput(
+                    "Every row needs a legacyCmisId or name".to_string(),
+                ));
+            }
+        };
+
+        marks.push(AttendanceMark {
+            legacy_cmis_id,
+            date,
+            attended: parse_attended(&row.attended)?,
+        });
+    }
+    Ok(marks)
+}
+
+/// The earliest and latest dates in an attendance CSV, to know which weeks to fetch from LCR
+/// before calling [`read_csv`].
+///
+/// # Errors
+/// Malformed CSV, dates that don't parse, or a CSV without any rows.
+pub fn csv_date_range<R: Read>(reader: R) -> Result<(Date, Date), Error> {
+    let mut range: Option<(Date, Date)> = None;
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: CsvRow = row?;
+        let date = parse_date(&row.date)
+            .ok_or_else(|| Error::InvalidInput(format!("Not a date: {}", row.date)))?;
+        range = Some(match range {
+            Some((start, end)) => (start.min(date), end.max(date)),
+            None => (date, date),
+        });
+    }
+    range.ok_or_else(|| Error::InvalidInput("The CSV has no rows".to_string()))
+}
+
+/// Reads a roll-call list with one name per line. Everyone listed was present on `date` and
+/// everyone else in the class was absent.
+///
+/// # Errors
+/// A `date` that isn't a Sunday, a list without any names, or names not in the class.
+pub fn read_roll_call(
+    text: &str,
+    date: Date,
+    attendance: &ClassAttendance,
+) -> Result<Vec<AttendanceMark>, Error> {
+    check_sunday(date)?;
+    let present = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| find_attendee(attendance, l).map(|a| a.legacy_cmis_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    // Marking the whole class absent is more likely the wrong file than an empty Sunday.
+    if present.is_empty() {
+        return Err(Error::InvalidInput(
+            "The roll-call list has no names".to_string(),
+        ));
+    }
+
+    Ok(attendance
+        .attendees
+        .iter()
+        .map(|a| AttendanceMark {
+            legacy_cmis_id: a.legacy_cmis_id,
+            date,
+            attended: present.contains(&a.legacy_cmis_id),
+        })
+        .collect())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceChange {
+    pub name: String,
+    pub legacy_cmis_id: u64,
+    pub date: String,
+    pub attended: bool,
+    /// What LCR had before, if anything.
+    pub previously: Option<bool>,
+}
+
+impl AttendanceChange {
+    pub fn to_update(&self) -> AttendanceUpdate {
+        AttendanceUpdate {
+            legacy_cmis_id: self.legacy_cmis_id,
+            date: self.date.clone(),
+            attended: self.attended,
+        }
+    }
+}
+
+/// The marks that differ from what LCR already has. Marking someone absent who has no entry
+/// isn't a change, since LCR treats a missing entry as absent.
+pub fn changes(attendance: &ClassAttendance, marks: &[AttendanceMark]) -> Vec<AttendanceChange> {
+    marks
+        .iter()
+        .filter_map(|mark| {
+            let attendee = attendance
+                .attendees
+                .iter()
+                .find(|a| a.legacy_cmis_id == mark.legacy_cmis_id);
+            let previously = attendee.and_then(|a| a.attended(mark.date));
+            if previously.unwrap_or(false) == mark.attended {
+                return None;
+            }
+
+            Some(AttendanceChange {
+                name: attendee
+                    .map(|a| a.name.clone())
+                    .unwrap_or_else(|| mark.legacy_cmis_id.to_string()),
+                legacy_cmis_id: mark.legacy_cmis_id,
+                date: mark.date.to_string(),
+                attended: mark.attended,
+                previously,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn attendance() -> ClassAttendance {
+        serde_json::from_str(
+            r#"{"attendees": [
+                {"legacyCmisId": 1, "displayName": "Doe, Jane",
+                 "entries": [{"date": "2025-06-01", "isMarkedAttended": true},
+                             {"date": "2025-06-08", "isMarkedAttended": false}]},
+                {"legacyCmisId": 2, "displayName": "Roe, Richard",
+                 "entries": [{"date": "2025-06-01", "isMarkedAttended": false}]}
+            ]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_roll_call_changes() {
+        let attendance = attendance();
+        let marks = read_roll_call("richard roe\n\n", date!(2025 - 06 - 01), &attendance).unwrap();
+        let changes = changes(&attendance, &marks);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].legacy_cmis_id, 1);
+        assert!(!changes[0].attended);
+        assert_eq!(changes[1].previously, Some(false));
+        assert!(changes[1].attended);
+
+        assert!(read_roll_call("Nobody", date!(2025 - 06 - 01), &attendance).is_err());
+        assert!(read_roll_call("\n \n", date!(2025 - 06 - 01), &attendance).is_err());
+        assert!(read_roll_call("Jane Doe", date!(2025 - 06 - 02), &attendance).is_err());
+    }
+
+    #[test]
+    fn test_find_attendee_ambiguous() {
+        let mut attendance = attendance();
+        let mut twin = attendance.attendees[0].clone();
+        twin.legacy_cmis_id = 3;
+        attendance.attendees.push(twin);
+
+        assert!(find_attendee(&attendance, "Jane Doe").is_err());
+        assert_eq!(
+            find_attendee(&attendance, "Richard Roe")
+                .unwrap()
+                .legacy_cmis_id,
+            2
+        );
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let attendance = attendance();
+        let csv =
+            "date,legacyCmisId,name,attended\n2025-06-08,,\"Doe, Jane\",x\n2025-06-01,1,,yes\n";
+        assert_eq!(
+            csv_date_range(csv.as_bytes()).unwrap(),
+            (date!(2025 - 06 - 01), date!(2025 - 06 - 08))
+        );
+        assert!(csv_date_range("date,legacyCmisId,name,attended\n".as_bytes()).is_err());
+        let (start, end) = (date!(2025 - 06 - 01), date!(2025 - 06 - 08));
+        let marks = read_csv(csv.as_bytes(), &attendance, start, end).unwrap();
+        assert_eq!(
+            marks[0],
+            AttendanceMark {
+                legacy_cmis_id: 1,
+                date: date!(2025 - 06 - 08),
+                attended: true
+            }
+        );
+
+        // Already marked present on the 1st, so only the 8th changes.
+        let changes = changes(&attendance, &marks);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].date, "2025-06-08");
+
+        let unknown_id = "date,legacyCmisId,name,attended\n2025-06-01,9,,yes\n";
+        assert!(read_csv(unknown_id.as_bytes(), &attendance, start, end).is_err());
+        let saturday = "date,legacyCmisId,name,attended\n2025-06-07,1,,yes\n";
+        assert!(read_csv(saturday.as_bytes(), &attendance, start, end).is_err());
+        let unfetched = "date,legacyCmisId,name,attended\n2025-06-15,1,,yes\n";
+        assert!(read_csv(unfetched.as_bytes(), &attendance, start, end).is_err());
+
+        // Nobody has an entry for the 15th yet, which is fine when it was fetched.
+        let unmarked = "date,legacyCmisId,name,attended\n2025-06-15,2,,yes\n";
+        let end = date!(2025 - 06 - 15);
+        assert!(read_csv(unmarked.as_bytes(), &attendance, start, end).is_ok());
+    }
+}
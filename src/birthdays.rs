@@ -0,0 +1,200 @@
+//! Upcoming birthdays and a shareable calendar of them, built from `Client::birthdays`.
+
+use serde::{Serialize, Serializer};
+use time::{Date, Month};
+
+use crate::data::Birthday;
+use crate::ics::{Calendar, Event, EventTime, Recurrence};
+
+// Birthdays without ages are put on this year, a leap year so February 29th exists.
+const AGELESS_YEAR: i32 = 2000;
+
+/// The birthday in `year`. February 29th birthdays fall on the 28th in other years.
+pub(crate) fn in_year(birth_date: Date, year: i32) -> Date {
+    Date::from_calendar_date(year, birth_date.month(), birth_date.day())
+        .or_else(|_| Date::from_calendar_date(year, Month::February, 28))
+        .expect("February 28th exists every year")
+}
+
+/// The next birthday on or after `today`.
+pub fn next_birthday(birth_date: Date, today: Date) -> Date {
+    let this_year = in_year(birth_date, today.year());
+    if this_year >= today {
+        this_year
+    } else {
+        in_year(birth_date, today.year() + 1)
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingBirthday<'a> {
+    pub name: &'a str,
+    pub legacy_cmis_id: Option<u64>,
+    #[serde(serialize_with = "serialize_date")]
+    pub date: Date,
+    /// The age they're turning, unless ages were left out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+}
+
+fn serialize_date<S: Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(date)
+}
+
+/// The first day after the `months` months starting with `today`'s month.
+fn window_end(today: Date, months: u8) -> Date {
+    let months_since_year_zero = today.year() * 12 + i32::from(u8::from(today.month())) - 1;
+    let end = months_since_year_zero + i32::from(months);
+    let month = Month::try_from((end % 12 + 1) as u8).expect("Always between 1 and 12");
+    Date::from_calendar_date(end / 12, month, 1).expect("The first of the month always exists")
+}
+
+/// Birthdays from `today` through the end of the `months` months starting with this one,
+/// soonest first. This is the window `Client::birthdays` fetches, so birthdays earlier this month
+/// are left out rather than moved to next year.
+pub fn upcoming(
+    birthdays: &[Birthday],
+    today: Date,
+    months: u8,
+    include_age: bool,
+) -> Vec<UpcomingBirthday<'_>> {
+    let end = window_end(today, months);
+    let mut upcoming: Vec<_> = birthdays
+        .iter()
+        .filter_map(|b| {
+            let birth_date = b.birth_date()?;
+            let date = next_birthday(birth_date, today);
+            if date >= end {
+                return None;
+            }
+            Some(UpcomingBirthday {
+                name: &b.name,
+                legacy_cmis_id: b.legacy_cmis_id,
+                date,
+                age: include_age.then(|| date.year() - birth_date.year()),
+            })
+        })
+        .collect();
+    upcoming.sort_by_key(|b| b.date);
+    upcoming
+}
+
+/// A calendar with a yearly event for each birthday. Only names and month/day are included
+/// unless `include_age`, in which case events start on the actual birth date so calendar apps
+/// can show ages. Birthdays without a birth date are left out.
+pub fn calendar(birthdays: &[Birthday], include_age: bool) -> Calendar {
+    let events = birthdays
+        .iter()
+        .filter_map(|b| {
+            let birth_date = b.birth_date()?;
+            let date = if include_age {
+                birth_date
+            } else {
+                in_year(birth_date, AGELESS_YEAR)
+            };
+            let uid = match b.legacy_cmis_id {
+                Some(id) => format!("birthday-{}@lcr", id),
+                None => format!("birthday-{}-{}@lcr", b.name.replace(' ', ""), date),
+            };
+
+            let recurrence = if date.month() == Month::February && date.day() == 29 {
+                Recurrence::YearlyLastDayOfFebruary
+            } else {
+                Recurrence::Yearly
+            };
+
+            Some(Event {
+                uid,
+                summary: format!("{}'s birthday", b.name),
+                description: None,
+                time: EventTime::AllDay(date),
+                recurrence: Some(recurrence),
+            })
+        })
+        .collect();
+
+    Calendar {
+        name: "Birthdays".to_string(),
+        events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_next_birthday() {
+        let today = date!(2025 - 06 - 15);
+        assert_eq!(
+            next_birthday(date!(1990 - 06 - 15), today),
+            date!(2025 - 06 - 15)
+        );
+        assert_eq!(
+            next_birthday(date!(1990 - 01 - 02), today),
+            date!(2026 - 01 - 02)
+        );
+        assert_eq!(
+            next_birthday(date!(1988 - 02 - 29), today),
+            date!(2026 - 02 - 28)
+        );
+    }
+
+    #[test]
+    fn test_window_end() {
+        assert_eq!(window_end(date!(2025 - 06 - 15), 1), date!(2025 - 07 - 01));
+        assert_eq!(window_end(date!(2025 - 11 - 30), 3), date!(2026 - 02 - 01));
+        assert_eq!(window_end(date!(2025 - 01 - 01), 12), date!(2026 - 01 - 01));
+    }
+
+    #[test]
+    fn test_calendar_omits_age() {
+        let birthdays = vec![
+            Birthday {
+                name: "Jane Doe".to_string(),
+                legacy_cmis_id: Some(1),
+                birth_date: Some("19880229".to_string()),
+                age: Some(37),
+            },
+            Birthday {
+                name: "No Date".to_string(),
+                legacy_cmis_id: Some(2),
+                birth_date: None,
+                age: None,
+            },
+        ];
+
+        let with_age = calendar(&birthdays, true);
+        assert_eq!(with_age.events.len(), 1);
+        assert_eq!(
+            with_age.events[0].time,
+            EventTime::AllDay(date!(1988 - 02 - 29))
+        );
+        assert_eq!(
+            with_age.events[0].recurrence,
+            Some(Recurrence::YearlyLastDayOfFebruary)
+        );
+
+        let next = upcoming(&birthdays, date!(2025 - 06 - 15), 12, true);
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].date, date!(2026 - 02 - 28));
+        assert_eq!(next[0].age, Some(38));
+        // February is past a three month window starting in June.
+        assert!(upcoming(&birthdays, date!(2025 - 06 - 15), 3, true).is_empty());
+        // Next year's birthday is in the last month of a twelve month window, not eleven.
+        assert_eq!(
+            upcoming(&birthdays, date!(2026 - 03 - 01), 12, true).len(),
+            1
+        );
+        assert!(upcoming(&birthdays, date!(2026 - 03 - 01), 11, true).is_empty());
+
+        let without_age = calendar(&birthdays, false);
+        assert_eq!(
+            without_age.events[0].time,
+            EventTime::AllDay(date!(2000 - 02 - 29))
+        );
+        assert_eq!(without_age.events[0].summary, "Jane Doe's birthday");
+    }
+}
@@ -0,0 +1,180 @@
+//! Tools built on top of the organizations and callings returned by `Client::callings`.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use time::Date;
+
+use crate::data::{Calling, MemberListPerson, Organization, PersonRef};
+
+/// One row per calling, for flat exports like CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallingRow<'a> {
+    pub organization: &'a str,
+    pub sub_organization: &'a str,
+    pub position: &'a str,
+    pub member_name: Option<&'a str>,
+    pub legacy_cmis_id: Option<u64>,
+    pub sustained_date: Option<String>,
+    pub set_apart_date: Option<String>,
+    pub vacant: bool,
+}
+
+pub fn calling_rows(orgs: &[Organization]) -> Vec<CallingRow<'_>> {
+    orgs.iter()
+        .flat_map(|org| {
+            org.all_callings()
+                .into_iter()
+                .map(move |(sub_org, calling)| row(org, sub_org, calling))
+        })
+        .collect()
+}
+
+fn row<'a>(
+    org: &'a Organization,
+    sub_org: &'a Organization,
+    calling: &'a Calling,
+) -> CallingRow<'a> {
+    CallingRow {
+        organization: &org.name,
+        sub_organization: &sub_org.name,
+        position: &calling.position,
+        member_name: calling.member_name.as_deref(),
+        legacy_cmis_id: calling.legacy_cmis_id,
+        sustained_date: calling.sustained_date().map(|d| d.to_string()),
+        set_apart_date: calling.set_apart_date().map(|d| d.to_string()),
+        vacant: calling.is_vacant(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipleCallings<'a> {
+    pub legacy_cmis_id: u64,
+    pub name: &'a str,
+    pub positions: Vec<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tenure<'a> {
+    pub legacy_cmis_id: u64,
+    pub name: &'a str,
+    pub position: &'a str,
+    pub months: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallingReport<'a> {
+    pub vacancies: Vec<CallingRow<'a>>,
+    pub multiple_callings: Vec<MultipleCallings<'a>>,
+    pub adults_without_calling: Vec<PersonRef>,
+    pub tenures: Vec<Tenure<'a>>,
+}
+
+/// Months are counted as four weeks, the same as time in ward in the `report` command.
+fn months_between(from: Date, to: Date) -> i64 {
+    (to - from).whole_weeks() / 4
+}
+
+pub fn calling_report<'a>(
+    orgs: &'a [Organization],
+    members: &[MemberListPerson],
+    today: Date,
+) -> CallingReport<'a> {
+    let rows = calling_rows(orgs);
+    let held: Vec<_> = orgs
+        .iter()
+        .flat_map(|org| org.all_callings())
+        .map(|(_, calling)| calling)
+        .filter_map(|c| Some((c.legacy_cmis_id?, c)))
+        .collect();
+
+    let mut by_person: BTreeMap<u64, Vec<&Calling>> = BTreeMap::new();
+    for (id, calling) in &held {
+        by_person.entry(*id).or_default().push(calling);
+    }
+    let multiple_callings = by_person
+        .iter()
+        .filter(|(_, callings)| callings.len() > 1)
+        .map(|(id, callings)| MultipleCallings {
+            legacy_cmis_id: *id,
+            name: callings[0].member_name.as_deref().unwrap_or("Unknown"),
+            positions: callings.iter().map(|c| c.position.as_str()).collect(),
+        })
+        .collect();
+
+    let called: HashSet<u64> = by_person.keys().copied().collect();
+    let mut adults_without_calling: Vec<_> = members
+        .iter()
+        .filter(|m| m.age >= 18 && !called.contains(&m.legacy_cmis_id))
+        .map(|m| PersonRef {
+            legacy_cmis_id: m.legacy_cmis_id,
+            name: m.name_list_preferred_local.clone(),
+        })
+        .collect();
+    adults_without_calling.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let tenures = held
+        .iter()
+        .filter_map(|(id, calling)| {
+            Some(Tenure {
+                legacy_cmis_id: *id,
+                name: calling.member_name.as_deref().unwrap_or("Unknown"),
+                position: &calling.position,
+                months: months_between(calling.sustained_date()?, today),
+            })
+        })
+        .collect();
+
+    CallingReport {
+        vacancies: rows.into_iter().filter(|r| r.vacant).collect(),
+        multiple_callings,
+        adults_without_calling,
+        tenures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_calling_report() {
+        let orgs: Vec<Organization> = serde_json::from_str(
+            r#"[{"subOrgId": 1, "name": "Primary", "callings": [
+                {"position": "President", "memberName": "Family, Given1", "memberId": 1, "activeDate": "20240101"},
+                {"position": "Pianist", "memberName": "Family, Given1", "memberId": 1},
+                {"position": "Secretary", "memberName": "Family, Given2", "memberId": 2, "activeDate": "20250301"},
+                {"position": "Teacher"}
+            ]}]"#,
+        )
+        .unwrap();
+        let members = vec![
+            MemberListPerson::test(1, 40, "F", "a"),
+            MemberListPerson::test(2, 30, "F", "b"),
+            MemberListPerson::test(3, 50, "M", "c"),
+            MemberListPerson::test(4, 12, "M", "c"),
+        ];
+
+        let report = calling_report(&orgs, &members, date!(2025 - 03 - 29));
+        assert_eq!(report.vacancies.len(), 1);
+        assert_eq!(report.vacancies[0].position, "Teacher");
+        assert_eq!(report.multiple_callings.len(), 1);
+        assert_eq!(
+            report.multiple_callings[0].positions,
+            vec!["President", "Pianist"]
+        );
+        let without: Vec<_> = report
+            .adults_without_calling
+            .iter()
+            .map(|p| p.legacy_cmis_id)
+            .collect();
+        assert_eq!(without, vec![3]);
+        let months: Vec<_> = report.tenures.iter().map(|t| t.months).collect();
+        assert_eq!(months, vec![16, 1]);
+    }
+}
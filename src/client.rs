@@ -1,6 +1,9 @@
+use crate::contact::{DEFAULT_CALLING_CODE, calling_code_for_country};
 use crate::data::{
-    EQMinisteringAssignments, MemberListPerson, MemberProfile, MovedInPerson, MovedOutPerson,
-    PhotoInfo, RSMinisteringAssignments, VisualPerson,
+    ActionInterviewList, AttendanceUpdate, Birthday, BirthdayMonth, ClassAttendance, Household,
+    MemberListPerson, MemberProfile, Ministering, MinisteringOrganization, MovedInPerson,
+    MovedOutPerson, Organization, PhotoInfo, RecommendStatus, SacramentAttendanceMonth,
+    SacramentAttendanceWeek, UnitDetails, UserUnits, VisualPerson,
 };
 use crate::error::{Error, HeadlessError};
 use headless_chrome::{
@@ -11,7 +14,8 @@ use headless_chrome::{
 use itertools::Itertools;
 
 use once_cell::sync::Lazy;
-use std::collections::{HashMap, HashSet};
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::thread::sleep;
 use std::time::Duration;
@@ -30,14 +34,26 @@ static HEADER_CHANNEL: Lazy<(MutexedHeaderSender, MutexedHeaderReceiver)> = Lazy
     (Mutex::new(tx), Mutex::new(rx))
 });
 
+// How many member profiles to fetch at once. Kept small to avoid hammering LCR.
+const PROFILE_FETCH_THREADS: usize = 8;
+// How many units to fetch at once for stake commands.
+const UNIT_FETCH_THREADS: usize = 4;
+
 #[derive(Debug, Clone)]
 pub struct ClientOptions {
     pub headless: bool,
+
+    /// Calling code used to normalize phone numbers entered without one, unless the member's
+    /// address names a known country. `None` uses the country of the unit's address.
+    pub default_calling_code: Option<u16>,
 }
 
 impl Default for ClientOptions {
     fn default() -> Self {
-        Self { headless: true }
+        Self {
+            headless: true,
+            default_calling_code: None,
+        }
     }
 }
 
@@ -48,6 +64,8 @@ pub struct Client {
     unit_number: String,
     headers: Option<Headers>,
     options: ClientOptions,
+    /// The unit's calling code, once looked up.
+    unit_calling_code: Option<u16>,
 }
 
 impl Client {
@@ -71,9 +89,79 @@ impl Client {
             unit_number: unit_number.into(),
             headers: None,
             options: client_options,
+            unit_calling_code: None,
+        }
+    }
+
+    pub fn unit_number(&self) -> &str {
+        &self.unit_number
+    }
+
+    /// Switches to the logged in user's home unit, for when the unit number isn't known ahead of
+    /// time. Returns the unit number.
+    pub fn use_home_unit(&mut self) -> Result<String> {
+        self.unit_number = self.units()?.home_unit_number.to_string();
+        self.unit_calling_code = None;
+        Ok(self.unit_number.clone())
+    }
+
+    /// A client for another unit that shares this one's login session.
+    pub fn for_unit(&self, unit_number: impl Into<String>) -> Client {
+        Client {
+            unit_number: unit_number.into(),
+            unit_calling_code: None,
+            ..self.clone()
         }
     }
 
+    /// Runs `f` for each unit, a few units at a time, sharing one login session. Results are in
+    /// the same order as `unit_numbers`, and the first error from any unit is returned.
+    pub fn per_unit<T, F>(&mut self, unit_numbers: &[String], f: F) -> Result<Vec<T>>
+    where
+        T: Send,
+        F: Fn(&mut Client) -> Result<T> + Sync,
+    {
+        self.fan_out(unit_numbers, UNIT_FETCH_THREADS, |client, unit_number| {
+            f(&mut client.for_unit(unit_number.as_str()))
+        })
+    }
+
+    /// Calls `f` for each item on at most `threads` threads, each with its own client sharing
+    /// one login session. Results are in the same order as `items`, and the first error is
+    /// returned.
+    fn fan_out<I, T, F>(&mut self, items: &[I], threads: usize, f: F) -> Result<Vec<T>>
+    where
+        I: Sync,
+        T: Send,
+        F: Fn(&mut Client, &I) -> Result<T> + Sync,
+    {
+        // Log in once up front so every thread shares the same session.
+        self.header_map()?;
+
+        let chunk_size = items.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = items
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let mut client = self.clone();
+                    let f = &f;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|item| f(&mut client, item))
+                            .collect::<Result<Vec<_>>>()
+                    })
+                })
+                .collect();
+
+            let mut results = Vec::with_capacity(items.len());
+            for handle in handles {
+                results.extend(handle.join().expect("Fetching thread panicked")?);
+            }
+            Ok(results)
+        })
+    }
+
     fn get(&mut self, url: &str) -> Result<Response<Body>> {
         let mut req = ureq::get(url);
         let headers = self.header_map()?;
@@ -85,6 +173,17 @@ impl Client {
         Ok(req.call()?)
     }
 
+    fn post<T: Serialize>(&mut self, url: &str, body: &T) -> Result<Response<Body>> {
+        let mut req = ureq::post(url);
+        let headers = self.header_map()?;
+        for (k, v) in headers {
+            req = req.header(k, v);
+        }
+        req = req.header("Accept", "application/json");
+
+        Ok(req.send_json(body)?)
+    }
+
     /// # Errors
     /// HTTP fetching errors for this specific call or for logging in the user specified by the credentials when this client was created.
     pub fn moved_in(&mut self, num_months: u8) -> Result<Vec<MovedInPerson>> {
@@ -115,40 +214,173 @@ impl Client {
             self.unit_number
         );
         let mut resp = self.get(&url)?;
-        let people: Vec<MemberListPerson> = resp.body_mut().read_json().map_err(Error::Http)?;
+        let mut people: Vec<MemberListPerson> = resp.body_mut().read_json().map_err(Error::Http)?;
+        let default_calling_code = self.default_calling_code()?;
+        for person in &mut people {
+            person.normalize_contact(default_calling_code);
+        }
         Ok(people)
     }
 
-    pub fn ministering_people(
-        &mut self,
-        from_eq: bool,
-        only_females: bool,
-    ) -> Result<HashSet<String>> {
-        let member_list = self.member_list()?;
-        let females_by_id: HashMap<u64, bool> = member_list
-            .into_iter()
-            .map(|m| (m.legacy_cmis_id, m.sex == "F"))
-            .collect();
+    pub fn households(&mut self) -> Result<Vec<Household>> {
+        let members = self.member_list()?;
+        let default_calling_code = self.default_calling_code()?;
+        Ok(Household::group(members, default_calling_code))
+    }
+
+    /// The calling code for phone numbers entered without one: the one from the options if set,
+    /// otherwise the one for the country in the unit's address, or [`DEFAULT_CALLING_CODE`] if
+    /// that isn't recognized.
+    fn default_calling_code(&mut self) -> Result<u16> {
+        if let Some(code) = self.options.default_calling_code.or(self.unit_calling_code) {
+            return Ok(code);
+        }
+
+        let unit_number = self.unit_number.clone();
+        let code = self
+            .unit_details(&unit_number)?
+            .address
+            .and_then(|a| a.parsed.country)
+            .and_then(|c| calling_code_for_country(&c))
+            .unwrap_or(DEFAULT_CALLING_CODE);
+        self.unit_calling_code = Some(code);
+        Ok(code)
+    }
 
+    pub fn ministering(&mut self, org: MinisteringOrganization) -> Result<Ministering> {
         let url = format!(
             "https://lcr.churchofjesuschrist.org/api/umlu/v1/ministering/data-full?lang=eng&type={}&unitNumber={}",
-            if from_eq { "EQ" } else { "RS" },
+            org.query_type(),
             self.unit_number
         );
         let mut resp = self.get(&url)?;
+        let ministering: Ministering = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(ministering)
+    }
 
-        let mut set = HashSet::new();
-        if from_eq {
-            let assignments: EQMinisteringAssignments =
-                resp.body_mut().read_json().map_err(Error::Http)?;
-            assignments.collect_unique_names(&mut set, only_females, &females_by_id);
-        } else {
-            let assignments: RSMinisteringAssignments =
-                resp.body_mut().read_json().map_err(Error::Http)?;
-            assignments.collect_unique_names(&mut set, only_females, &females_by_id);
+    /// Birthdays in the `num_months` months starting with `start_month`, in date order.
+    pub fn birthdays(&mut self, start_month: time::Month, num_months: u8) -> Result<Vec<Birthday>> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/report/birthday-list?unitNumber={}&month={}&months={}&lang=eng",
+            self.unit_number,
+            u8::from(start_month),
+            num_months
+        );
+        let mut resp = self.get(&url)?;
+        let months: Vec<BirthdayMonth> = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(months.into_iter().flat_map(|m| m.birthdays).collect())
+    }
+
+    /// Weekly attendance for a class or quorum, identified by its `sub_org_id` from
+    /// [`Client::callings`], for Sundays between `start` and `end` inclusive.
+    pub fn class_attendance(
+        &mut self,
+        sub_org_id: u64,
+        start: time::Date,
+        end: time::Date,
+    ) -> Result<ClassAttendance> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/umlu/v1/class-and-quorum/attendance/overview/unitNumber/{}/subOrgId/{}/start/{}/end/{}?lang=eng",
+            self.unit_number, sub_org_id, start, end
+        );
+        let mut resp = self.get(&url)?;
+        let attendance: ClassAttendance = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(attendance)
+    }
+
+    /// Marks people present or absent in a class or quorum.
+    pub fn record_attendance(
+        &mut self,
+        sub_org_id: u64,
+        updates: &[AttendanceUpdate],
+    ) -> Result<()> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/umlu/v1/class-and-quorum/attendance/unitNumber/{}/subOrgId/{}?lang=eng",
+            self.unit_number, sub_org_id
+        );
+        for update in updates {
+            self.post(&url, update)?;
         }
+        Ok(())
+    }
+
+    /// Weekly sacrament meeting attendance counts for every Sunday of `year`.
+    pub fn sacrament_attendance(&mut self, year: i32) -> Result<Vec<SacramentAttendanceWeek>> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/sacrament-attendance/unit/{}/years/{}?lang=eng",
+            self.unit_number, year
+        );
+        let mut resp = self.get(&url)?;
+        let months: Vec<SacramentAttendanceMonth> =
+            resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(months.into_iter().flat_map(|m| m.weeks).collect())
+    }
+
+    /// Submits the sacrament meeting attendance count for the Sunday on `date`.
+    pub fn record_sacrament_attendance(&mut self, date: time::Date, count: u32) -> Result<()> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/sacrament-attendance/unit/{}?lang=eng",
+            self.unit_number
+        );
+        let week = SacramentAttendanceWeek {
+            date: date.to_string(),
+            count: Some(count),
+        };
+        self.post(&url, &week)?;
+        Ok(())
+    }
+
+    /// The Action and Interview List: youth interviews, recommends, upcoming baptisms and so on.
+    pub fn action_interview_list(&mut self) -> Result<ActionInterviewList> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/report/action-interview-list/full/unit/{}?lang=eng",
+            self.unit_number
+        );
+        let mut resp = self.get(&url)?;
+        let list: ActionInterviewList = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(list)
+    }
+
+    /// Every unit the logged in user can access, along with their home unit.
+    pub fn units(&mut self) -> Result<UserUnits> {
+        let mut resp = self.get("https://lcr.churchofjesuschrist.org/api/user/units?lang=eng")?;
+        let units: UserUnits = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(units)
+    }
+
+    /// Name, address and meeting times of a unit, which doesn't have to be this client's.
+    pub fn unit_details(&mut self, unit_number: &str) -> Result<UnitDetails> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/unit/details/{}?lang=eng",
+            unit_number
+        );
+        let mut resp = self.get(&url)?;
+        let details: UnitDetails = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(details)
+    }
+
+    /// Recommend type and expiration for every adult member, from the temple recommend status
+    /// report.
+    pub fn recommend_status(&mut self) -> Result<Vec<RecommendStatus>> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/temple-recommend/report?unitNumber={}&lang=eng",
+            self.unit_number
+        );
+        let mut resp = self.get(&url)?;
+        let statuses: Vec<RecommendStatus> = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(statuses)
+    }
 
-        Ok(set)
+    /// Every organization in the unit with its sub-organizations and callings, as shown on the
+    /// "Callings by Organization" page.
+    pub fn callings(&mut self) -> Result<Vec<Organization>> {
+        let url = format!(
+            "https://lcr.churchofjesuschrist.org/api/orgs/sub-orgs-with-callings?lang=eng&unitNumber={}",
+            self.unit_number
+        );
+        let mut resp = self.get(&url)?;
+        let orgs: Vec<Organization> = resp.body_mut().read_json().map_err(Error::Http)?;
+        Ok(orgs)
     }
 
     pub fn visual_member_list(&mut self) -> Result<Vec<VisualPerson>> {
@@ -178,6 +410,7 @@ impl Client {
 
                 VisualPerson {
                     name: household.spoken_name.clone(),
+                    legacy_cmis_id: individual.legacy_cmis_id,
                     photo_url,
                 }
             })
@@ -185,6 +418,13 @@ impl Client {
         Ok(result)
     }
 
+    /// Downloads a photo from the visual member list.
+    pub fn photo(&mut self, url: &str) -> Result<Vec<u8>> {
+        let mut resp = self.get(url)?;
+        let bytes = resp.body_mut().read_to_vec().map_err(Error::Http)?;
+        Ok(bytes)
+    }
+
     pub fn member_profile(&mut self, legacy_cmis_id: u64) -> Result<MemberProfile> {
         let url = format!(
             "https://lcr.churchofjesuschrist.org/api/records/member-profile/service/{}?lang=eng",
@@ -195,6 +435,14 @@ impl Client {
         Ok(profile)
     }
 
+    /// Fetches several member profiles at once, a few at a time in parallel. Results are in the
+    /// same order as `legacy_cmis_ids`.
+    pub fn member_profiles(&mut self, legacy_cmis_ids: &[u64]) -> Result<Vec<MemberProfile>> {
+        self.fan_out(legacy_cmis_ids, PROFILE_FETCH_THREADS, |client, id| {
+            client.member_profile(*id)
+        })
+    }
+
     fn header_map(&mut self) -> Result<&Headers> {
         if self.headers.is_none() {
             let headers = self.login()?;
@@ -292,6 +540,7 @@ impl Client {
 }
 
 #[cfg(test)]
+#[allow(clippy::len_zero)]
 mod tests {
     use super::*;
     use std::env;
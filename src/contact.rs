@@ -0,0 +1,211 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// Calling code used for numbers without one when the unit's country isn't known.
+pub const DEFAULT_CALLING_CODE: u16 = 1;
+
+// LCR clerks mark do-not-contact by tacking "DNC" onto the email or phone field.
+static DO_NOT_CONTACT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bDNC\b|(?i:\bdo\s*not\s*contact\b)").unwrap());
+
+static EMAIL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[a-z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)+$").unwrap()
+});
+
+/// A phone number in E.164 format, e.g. `+18015551234`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PhoneNumber(String);
+
+impl PhoneNumber {
+    /// Normalizes a phone number as typed into LCR. Numbers without a leading `+` or `00` are
+    /// assumed to be local to the country with `default_calling_code`.
+    pub fn parse(raw: &str, default_calling_code: u16) -> Option<Self> {
+        let raw = raw.trim();
+        let digits: String = raw.chars().filter(char::is_ascii_digit).collect();
+
+        let full = if raw.starts_with('+') {
+            digits
+        } else if let Some(international) = digits.strip_prefix("00") {
+            international.to_string()
+        } else if default_calling_code == 1 {
+            // North American numbers are often written with the leading 1.
+            match digits.len() {
+                10 => format!("1{}", digits),
+                11 if digits.starts_with('1') => digits,
+                _ => return None,
+            }
+        } else {
+            // Drop the national trunk prefix most other countries use.
+            format!("{}{}", default_calling_code, digits.trim_start_matches('0'))
+        };
+
+        if (8..=15).contains(&full.len()) && !full.starts_with('0') {
+            Some(Self(format!("+{}", full)))
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A syntactically valid, lowercased email address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Email(String);
+
+impl Email {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let email = raw.trim().to_lowercase();
+        EMAIL.is_match(&email).then_some(Self(email))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Normalized contact info for a member or household.
+///
+/// When LCR marks someone do-not-contact, the flag is kept but the phone number and email are
+/// dropped, so nothing built on top of this can contact them by accident.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactInfo {
+    pub phone_number: Option<PhoneNumber>,
+    pub email: Option<Email>,
+    pub do_not_contact: bool,
+}
+
+impl ContactInfo {
+    pub fn from_raw(
+        phone_number: Option<&str>,
+        email: Option<&str>,
+        default_calling_code: u16,
+    ) -> Self {
+        let do_not_contact = phone_number
+            .into_iter()
+            .chain(email)
+            .any(is_marked_do_not_contact);
+
+        if do_not_contact {
+            return Self::do_not_contact();
+        }
+
+        Self {
+            phone_number: phone_number.and_then(|p| PhoneNumber::parse(p, default_calling_code)),
+            email: email.and_then(Email::parse),
+            do_not_contact,
+        }
+    }
+
+    pub fn do_not_contact() -> Self {
+        Self {
+            phone_number: None,
+            email: None,
+            do_not_contact: true,
+        }
+    }
+}
+
+/// Whether a raw phone or email field has been marked do-not-contact.
+pub fn is_marked_do_not_contact(field: &str) -> bool {
+    DO_NOT_CONTACT.is_match(field)
+}
+
+/// Looks up the calling code for a country as LCR spells it in addresses.
+pub fn calling_code_for_country(country: &str) -> Option<u16> {
+    let code = match country.trim().to_lowercase().as_str() {
+        "united states" | "united states of america" | "usa" | "canada" => 1,
+        "mexico" => 52,
+        "united kingdom" | "england" | "scotland" | "wales" => 44,
+        "ireland" => 353,
+        "germany" => 49,
+        "france" => 33,
+        "spain" => 34,
+        "italy" => 39,
+        "brazil" => 55,
+        "argentina" => 54,
+        "chile" => 56,
+        "peru" => 51,
+        "philippines" => 63,
+        "japan" => 81,
+        "south korea" | "korea" => 82,
+        "australia" => 61,
+        "new zealand" => 64,
+        "samoa" => 685,
+        "tonga" => 676,
+        "south africa" => 27,
+        "nigeria" => 234,
+        "ghana" => 233,
+        _ => return None,
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_phone_number() {
+        let parse = |raw| PhoneNumber::parse(raw, 1).map(|p| p.to_string());
+        assert_eq!(parse("(801) 555-1234"), Some("+18015551234".to_string()));
+        assert_eq!(parse("1-801-555-1234"), Some("+18015551234".to_string()));
+        assert_eq!(parse("+44 20 7946 0958"), Some("+442079460958".to_string()));
+        assert_eq!(
+            parse("0044 20 7946 0958"),
+            Some("+442079460958".to_string())
+        );
+        assert_eq!(parse("555-1234"), None);
+        assert_eq!(parse(""), None);
+
+        assert_eq!(
+            PhoneNumber::parse("020 7946 0958", 44).map(|p| p.to_string()),
+            Some("+442079460958".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_email() {
+        assert_eq!(
+            Email::parse(" John.Doe@Example.com ").map(|e| e.to_string()),
+            Some("john.doe@example.com".to_string())
+        );
+        assert_eq!(Email::parse("not an email"), None);
+        assert_eq!(Email::parse("john@localhost"), None);
+    }
+
+    #[test]
+    fn test_do_not_contact() {
+        let contact = ContactInfo::from_raw(Some("801-555-1234"), Some("DNC john@example.com"), 1);
+        assert!(contact.do_not_contact);
+        assert_eq!(contact.phone_number, None);
+        assert_eq!(contact.email, None);
+
+        let contact = ContactInfo::from_raw(Some("Do not contact"), None, 1);
+        assert!(contact.do_not_contact);
+
+        // Lowercase "dnc" inside an address is just part of the address.
+        let contact = ContactInfo::from_raw(None, Some("dnc.fan@example.com"), 1);
+        assert!(!contact.do_not_contact);
+        assert_eq!(contact.email.unwrap().as_str(), "dnc.fan@example.com");
+    }
+}
@@ -1,12 +1,19 @@
 use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 use time::macros::format_description;
 
+use crate::address::ParsedAddress;
+use crate::contact::{ContactInfo, calling_code_for_country, is_marked_do_not_contact};
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MovedOutPerson {
     pub name: String,
+    #[serde(default)]
+    pub legacy_cmis_id: Option<u64>,
     pub move_date_display: String,
     pub next_unit_name: Option<String>,
 }
@@ -15,36 +22,286 @@ pub struct MovedOutPerson {
 #[serde(rename_all = "camelCase")]
 pub struct MovedInPerson {
     pub name: String,
+    #[serde(default)]
+    pub legacy_cmis_id: Option<u64>,
     pub move_date: String,
     pub prior_unit_name: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", from = "RawAddress")]
 pub struct Address {
     pub address_lines: Vec<String>,
+    pub parsed: ParsedAddress,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAddress {
+    address_lines: Vec<String>,
+}
+
+impl From<RawAddress> for Address {
+    fn from(raw: RawAddress) -> Self {
+        let parsed = ParsedAddress::parse(&raw.address_lines);
+        Self {
+            address_lines: raw.address_lines,
+            parsed,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MemberListPerson {
     pub address: Option<Address>,
     pub age: u8,
     pub convert: bool,
+    // Raw contact fields are never exported; use `contact` instead, which honors do-not-contact.
+    #[serde(skip_serializing)]
     pub email: Option<String>,
+    #[serde(skip_serializing)]
     pub phone_number: Option<String>,
     pub sex: String,
     pub legacy_cmis_id: u64,
+    #[serde(default)]
+    pub birth_date: Option<String>,
 
     pub name_given_preferred_local: String,
     pub name_family_preferred_local: String,
     pub name_list_preferred_local: String,
+
+    pub household_uuid: Option<String>,
+    pub household_role: Option<HouseholdRole>,
+    #[serde(skip_serializing)]
+    pub household_email: Option<String>,
+    #[serde(skip_serializing)]
+    pub household_phone_number: Option<String>,
+
+    /// Filled in from the raw contact fields by `normalize_contact`.
+    #[serde(default)]
+    pub contact: ContactInfo,
+}
+
+/// Someone by ID, with a name to show for them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersonRef {
+    pub legacy_cmis_id: u64,
+    pub name: String,
+}
+
+impl PersonRef {
+    /// Prefers the member list name, falling back to `other_names` (like the names in ministering
+    /// data) for people who have since moved out.
+    pub fn resolve(
+        legacy_cmis_id: u64,
+        members_by_id: &HashMap<u64, &MemberListPerson>,
+        other_names: &HashMap<u64, &str>,
+    ) -> Self {
+        let name = members_by_id
+            .get(&legacy_cmis_id)
+            .map(|m| m.name_list_preferred_local.as_str())
+            .or_else(|| other_names.get(&legacy_cmis_id).copied())
+            .unwrap_or("Unknown");
+
+        Self {
+            legacy_cmis_id,
+            name: name.to_string(),
+        }
+    }
+}
+
+// Written out by hand so the raw contact fields don't leak into plaintext output either.
+impl fmt::Debug for MemberListPerson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemberListPerson")
+            .field("address", &self.address)
+            .field("age", &self.age)
+            .field("convert", &self.convert)
+            .field("sex", &self.sex)
+            .field("legacy_cmis_id", &self.legacy_cmis_id)
+            .field("birth_date", &self.birth_date)
+            .field(
+                "name_given_preferred_local",
+                &self.name_given_preferred_local,
+            )
+            .field(
+                "name_family_preferred_local",
+                &self.name_family_preferred_local,
+            )
+            .field("name_list_preferred_local", &self.name_list_preferred_local)
+            .field("household_uuid", &self.household_uuid)
+            .field("household_role", &self.household_role)
+            .field("contact", &self.contact)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MemberListPerson {
+    pub fn index_by_id(members: &[MemberListPerson]) -> HashMap<u64, &MemberListPerson> {
+        members.iter().map(|m| (m.legacy_cmis_id, m)).collect()
+    }
+
+    pub fn birth_date(&self) -> Option<time::Date> {
+        self.birth_date.as_deref().and_then(parse_date)
+    }
+
+    /// The calling code of the country in this member's address, falling back to
+    /// `default_calling_code` when there's no address or the country isn't recognized.
+    pub fn calling_code(&self, default_calling_code: u16) -> u16 {
+        self.address
+            .as_ref()
+            .and_then(|a| a.parsed.country.as_deref())
+            .and_then(calling_code_for_country)
+            .unwrap_or(default_calling_code)
+    }
+
+    pub fn normalize_contact(&mut self, default_calling_code: u16) {
+        self.contact = ContactInfo::from_raw(
+            self.phone_number.as_deref(),
+            self.email.as_deref(),
+            self.calling_code(default_calling_code),
+        );
+    }
+
+    /// Whether the raw phone or email is marked do-not-contact. Unlike `contact`, this doesn't
+    /// depend on `normalize_contact` having been called.
+    pub fn is_do_not_contact(&self) -> bool {
+        self.phone_number
+            .iter()
+            .chain(&self.email)
+            .any(|field| is_marked_do_not_contact(field))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HouseholdRole {
+    Head,
+    Spouse,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Household {
+    pub uuid: String,
+    pub head: MemberListPerson,
+    pub spouse: Option<MemberListPerson>,
+    /// Everyone else living in the household. Usually children, but LCR lumps any other
+    /// relationship in here too.
+    pub children: Vec<MemberListPerson>,
+    pub address: Option<Address>,
+    pub contact: ContactInfo,
+}
+
+impl Household {
+    /// Groups a flat member list into households, keeping the order in which each household
+    /// first appears. Members LCR doesn't put in a household end up in one of their own.
+    pub fn group(members: Vec<MemberListPerson>, default_calling_code: u16) -> Vec<Household> {
+        let mut order = Vec::new();
+        let mut by_uuid: HashMap<String, Vec<MemberListPerson>> = HashMap::new();
+        for member in members {
+            let uuid = member
+                .household_uuid
+                .clone()
+                .unwrap_or_else(|| member.legacy_cmis_id.to_string());
+            by_uuid
+                .entry(uuid.clone())
+                .or_insert_with(|| {
+                    order.push(uuid);
+                    Vec::new()
+                })
+                .push(member);
+        }
+
+        order
+            .into_iter()
+            .filter_map(|uuid| {
+                let members = by_uuid.remove(&uuid)?;
+                Some(Self::from_members(uuid, members, default_calling_code))
+            })
+            .collect()
+    }
+
+    fn from_members(
+        uuid: String,
+        mut members: Vec<MemberListPerson>,
+        default_calling_code: u16,
+    ) -> Household {
+        // Oldest first, so if LCR didn't mark a head we fall back to the oldest member.
+        members.sort_by_key(|m| std::cmp::Reverse(m.age));
+
+        let head_index = members
+            .iter()
+            .position(|m| m.household_role == Some(HouseholdRole::Head))
+            .unwrap_or(0);
+        let head = members.remove(head_index);
+
+        let spouse = members
+            .iter()
+            .position(|m| m.household_role == Some(HouseholdRole::Spouse))
+            .map(|i| members.remove(i));
+
+        let address = head
+            .address
+            .clone()
+            .or_else(|| spouse.as_ref().and_then(|s| s.address.clone()));
+        let phone_number = head
+            .household_phone_number
+            .as_deref()
+            .or(head.phone_number.as_deref())
+            .or_else(|| spouse.as_ref().and_then(|s| s.phone_number.as_deref()));
+        let email = head
+            .household_email
+            .as_deref()
+            .or(head.email.as_deref())
+            .or_else(|| spouse.as_ref().and_then(|s| s.email.as_deref()));
+
+        // A do-not-contact head or spouse means the whole household is.
+        let do_not_contact = std::iter::once(&head)
+            .chain(spouse.as_ref())
+            .any(|m| m.contact.do_not_contact || m.is_do_not_contact());
+        let contact = if do_not_contact {
+            ContactInfo::do_not_contact()
+        } else {
+            ContactInfo::from_raw(phone_number, email, head.calling_code(default_calling_code))
+        };
+
+        Household {
+            uuid,
+            head,
+            spouse,
+            children: members,
+            address,
+            contact,
+        }
+    }
+
+    /// Key for sorting households by street, using the parsed address. Households without an
+    /// address sort last.
+    pub fn street_sort_key(&self) -> (bool, (String, u64, String)) {
+        match &self.address {
+            Some(address) => (false, address.parsed.street_sort_key()),
+            None => (true, Default::default()),
+        }
+    }
+
+    pub fn members(&self) -> impl Iterator<Item = &MemberListPerson> {
+        std::iter::once(&self.head)
+            .chain(self.spouse.iter())
+            .chain(self.children.iter())
+    }
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PhotoInfo {
     pub spoken_name: String,
+    #[serde(default)]
+    pub legacy_cmis_id: Option<u64>,
     pub image: Image,
 }
 
@@ -57,6 +314,7 @@ pub struct Image {
 #[derive(Debug)]
 pub struct VisualPerson {
     pub name: String,
+    pub legacy_cmis_id: Option<u64>,
     pub photo_url: String,
 }
 
@@ -64,6 +322,70 @@ pub struct VisualPerson {
 #[serde(rename_all = "camelCase")]
 pub struct MemberProfile {
     pub individual: MemberProfileIndividual,
+    #[serde(default)]
+    pub ordinances: Vec<Ordinance>,
+}
+
+impl MemberProfile {
+    pub fn has_ordinance(&self, ordinance_type: OrdinanceType) -> bool {
+        self.ordinances
+            .iter()
+            .any(|o| o.ordinance_type == ordinance_type)
+    }
+
+    pub fn is_endowed(&self) -> bool {
+        self.individual.endowed.unwrap_or(false) || self.has_ordinance(OrdinanceType::Endowment)
+    }
+
+    pub fn is_sealed_to_spouse(&self) -> bool {
+        self.has_ordinance(OrdinanceType::SealedToSpouse)
+    }
+}
+
+/// An ordinance on a member's record. Priesthood ordinations also carry the office, like
+/// `Elder` or `Priest`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ordinance {
+    #[serde(rename = "type")]
+    pub ordinance_type: OrdinanceType,
+    pub date: Option<String>,
+    pub office: Option<String>,
+}
+
+impl Ordinance {
+    pub fn date(&self) -> Option<time::Date> {
+        self.date.as_deref().and_then(parse_date)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OrdinanceType {
+    Baptism,
+    Confirmation,
+    AaronicPriesthood,
+    MelchizedekPriesthood,
+    Endowment,
+    SealedToSpouse,
+    SealedToParents,
+    #[serde(other)]
+    Other,
+}
+
+impl Display for OrdinanceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Baptism => write!(f, "Baptism"),
+            Self::Confirmation => write!(f, "Confirmation"),
+            Self::AaronicPriesthood => write!(f, "Aaronic Priesthood"),
+            Self::MelchizedekPriesthood => write!(f, "Melchizedek Priesthood"),
+            Self::Endowment => write!(f, "Endowment"),
+            Self::SealedToSpouse => write!(f, "Sealed to spouse"),
+            Self::SealedToParents => write!(f, "Sealed to parents"),
+            Self::Other => write!(f, "Other"),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -77,114 +399,912 @@ pub struct MemberProfileIndividual {
 
 impl MemberProfileIndividual {
     pub fn move_date(&self) -> Option<time::Date> {
-        let date_format = format_description!("[year][month][day]");
-        self.move_date
-            .as_ref()
-            .and_then(|m| time::Date::parse(m, &date_format).ok())
+        self.move_date.as_deref().and_then(parse_date)
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// Parses the dates LCR returns, which are usually `20190512` but sometimes `2019-05-12`.
+pub fn parse_date(s: &str) -> Option<time::Date> {
+    let compact = format_description!("[year][month][day]");
+    let dashed = format_description!("[year]-[month]-[day]");
+    time::Date::parse(s, &compact)
+        .or_else(|_| time::Date::parse(s, &dashed))
+        .ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct RSMinisteringAssignments {
-    relief_society: Vec<QuorumOrClass>,
+pub enum Sex {
+    Male,
+    Female,
 }
 
-impl RSMinisteringAssignments {
-    pub fn collect_unique_names(
-        &self,
-        set: &mut HashSet<String>,
-        only_females: bool,
-        females_by_id: &HashMap<u64, bool>,
-    ) {
-        for class in &self.relief_society {
-            class.collect_unique_names(set, only_females, females_by_id);
+impl Sex {
+    pub fn matches(self, person: &MemberListPerson) -> bool {
+        let sex = match self {
+            Self::Male => "M",
+            Self::Female => "F",
+        };
+        person.sex.eq_ignore_ascii_case(sex)
+    }
+}
+
+impl FromStr for Sex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "m" | "male" => Ok(Self::Male),
+            "f" | "female" => Ok(Self::Female),
+            _ => Err(format!("Unknown sex: {}", s)),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// An organization that ministers. These are the only two LCR tracks ministering for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct EQMinisteringAssignments {
-    elders: Vec<QuorumOrClass>,
+pub enum MinisteringOrganization {
+    EldersQuorum,
+    ReliefSociety,
 }
 
-impl EQMinisteringAssignments {
-    pub fn collect_unique_names(
-        &self,
-        set: &mut HashSet<String>,
-        only_females: bool,
-        females_by_id: &HashMap<u64, bool>,
-    ) {
-        for quorum in &self.elders {
-            quorum.collect_unique_names(set, only_females, females_by_id);
+impl MinisteringOrganization {
+    pub const ALL: [Self; 2] = [Self::EldersQuorum, Self::ReliefSociety];
+
+    /// Whether someone is expected to be serving in a companionship. Youth can serve as
+    /// companions too, but nobody counts them as missing if they aren't.
+    pub fn is_eligible_minister(self, person: &MemberListPerson) -> bool {
+        let sex = match self {
+            Self::EldersQuorum => Sex::Male,
+            Self::ReliefSociety => Sex::Female,
+        };
+        person.age >= 18 && sex.matches(person)
+    }
+
+    /// Value of the `type` query parameter the ministering endpoints take.
+    pub fn query_type(self) -> &'static str {
+        match self {
+            Self::EldersQuorum => "EQ",
+            Self::ReliefSociety => "RS",
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+impl Display for MinisteringOrganization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EldersQuorum => write!(f, "Elders Quorum"),
+            Self::ReliefSociety => write!(f, "Relief Society"),
+        }
+    }
+}
+
+impl FromStr for MinisteringOrganization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "eq" | "elders" | "eldersquorum" => Ok(Self::EldersQuorum),
+            "rs" | "reliefsociety" => Ok(Self::ReliefSociety),
+            _ => Err(format!("Unknown ministering organization: {}", s)),
+        }
+    }
+}
+
+/// Ministering data for one organization. LCR names the top-level list after the organization,
+/// so accept either name.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ministering {
+    #[serde(alias = "elders", alias = "reliefSociety")]
+    pub quorums: Vec<QuorumOrClass>,
+}
+
+impl Ministering {
+    pub fn all_companionships(&self) -> impl Iterator<Item = &Companionship> {
+        self.quorums.iter().flat_map(|q| q.all_companionships())
+    }
+
+    /// Everyone in the ministering data, ministers and assignments both.
+    pub fn ids(&self) -> HashSet<u64> {
+        self.quorums.iter().flat_map(|q| q.ids()).collect()
+    }
+
+    /// Names of everyone in the ministering data, as LCR displays them here. Useful for people
+    /// who have since left the member list.
+    pub fn names_by_id(&self) -> HashMap<u64, &str> {
+        let mut names = HashMap::new();
+        for companionship in self.all_companionships() {
+            for minister in &companionship.ministers {
+                names.insert(minister.legacy_cmis_id, minister.name.as_str());
+            }
+            for assignment in companionship.assignments() {
+                names.insert(assignment.legacy_cmis_id, assignment.name.as_str());
+            }
+        }
+        names
+    }
+}
+
+/// An elders quorum or Relief Society class. LCR nests companionships in districts when the
+/// unit has set them up, otherwise they hang directly off the quorum or class.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QuorumOrClass {
-    companionships: Vec<Companionship>,
+    #[serde(default)]
+    pub districts: Vec<District>,
+    #[serde(default)]
+    pub companionships: Vec<Companionship>,
 }
 
 impl QuorumOrClass {
-    pub fn collect_unique_names(
+    /// All companionships, whether or not they're in a district.
+    pub fn all_companionships(&self) -> impl Iterator<Item = &Companionship> {
+        self.companionships_by_district().map(|(_, c)| c)
+    }
+
+    /// All companionships along with the district they're in, if any.
+    pub fn companionships_by_district(
         &self,
-        set: &mut HashSet<String>,
-        only_females: bool,
-        females_by_id: &HashMap<u64, bool>,
-    ) {
-        for companionship in &self.companionships {
-            companionship.collect_unique_names(set, only_females, females_by_id);
-        }
+    ) -> impl Iterator<Item = (Option<&District>, &Companionship)> {
+        self.districts
+            .iter()
+            .flat_map(|d| d.companionships.iter().map(move |c| (Some(d), c)))
+            .chain(self.companionships.iter().map(|c| (None, c)))
+    }
+
+    pub fn ids(&self) -> HashSet<u64> {
+        self.all_companionships().flat_map(|c| c.ids()).collect()
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct District {
+    pub district_name: String,
+    pub supervisor_name: Option<String>,
+    pub supervisor_legacy_cmis_id: Option<u64>,
+    #[serde(default)]
+    pub companionships: Vec<Companionship>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Companionship {
-    ministers: Vec<MinisteringCompanionship>,
-    assignments: Option<Vec<MinisteringAssignment>>,
+    pub id: Option<String>,
+    pub ministers: Vec<Minister>,
+    pub assignments: Option<Vec<MinisteringAssignment>>,
 }
 
 impl Companionship {
-    pub fn collect_unique_names(
-        &self,
-        set: &mut HashSet<String>,
-        only_females: bool,
-        females_by_id: &HashMap<u64, bool>,
-    ) {
-        for minister in &self.ministers {
-            let is_female = females_by_id.get(&minister.legacy_cmis_id).unwrap_or(&true);
-            if !only_females || *is_female {
-                set.insert(minister.name.to_string());
-            }
+    pub fn assignments(&self) -> &[MinisteringAssignment] {
+        self.assignments.as_deref().unwrap_or_default()
+    }
+
+    pub fn minister_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.ministers.iter().map(|m| m.legacy_cmis_id)
+    }
+
+    pub fn assigned_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.assignments().iter().map(|a| a.legacy_cmis_id)
+    }
+
+    pub fn ids(&self) -> HashSet<u64> {
+        self.minister_ids().chain(self.assigned_ids()).collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Minister {
+    pub name: String,
+    pub legacy_cmis_id: u64,
+}
+
+/// A household or individual assigned to a companionship. For households, `legacy_cmis_id` is
+/// the head of household.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinisteringAssignment {
+    pub name: String,
+    pub legacy_cmis_id: u64,
+}
+
+/// Weekly class or quorum attendance for one organization.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAttendance {
+    #[serde(default)]
+    pub attendees: Vec<Attendee>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attendee {
+    pub legacy_cmis_id: u64,
+    #[serde(alias = "displayName")]
+    pub name: String,
+    #[serde(default)]
+    pub entries: Vec<AttendanceEntry>,
+}
+
+impl Attendee {
+    pub fn attended(&self, date: time::Date) -> Option<bool> {
+        self.entries
+            .iter()
+            .find(|e| e.date() == Some(date))
+            .map(|e| e.attended)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceEntry {
+    pub date: String,
+    #[serde(alias = "isMarkedAttended")]
+    pub attended: bool,
+}
+
+impl AttendanceEntry {
+    pub fn date(&self) -> Option<time::Date> {
+        parse_date(&self.date)
+    }
+}
+
+/// Marks one person present or absent on one Sunday, as sent to LCR.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceUpdate {
+    pub legacy_cmis_id: u64,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    #[serde(rename = "isMarkedAttended")]
+    pub attended: bool,
+}
+
+/// The units the logged in user can see in LCR, and which one is their own.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUnits {
+    pub home_unit_number: u64,
+    #[serde(default)]
+    pub units: Vec<Unit>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Unit {
+    pub unit_number: u64,
+    #[serde(alias = "unitName")]
+    pub name: String,
+    /// Like `WARD`, `BRANCH` or `STAKE`.
+    pub unit_type: Option<String>,
+    pub parent_unit_number: Option<u64>,
+    pub parent_unit_name: Option<String>,
+}
+
+impl Unit {
+    pub fn is_ward_or_branch(&self) -> bool {
+        self.unit_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("ward") || t.eq_ignore_ascii_case("branch"))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitDetails {
+    pub unit_number: u64,
+    #[serde(alias = "unitName")]
+    pub name: String,
+    pub unit_type: Option<String>,
+    pub address: Option<Address>,
+    #[serde(default)]
+    pub meeting_times: Vec<MeetingTime>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingTime {
+    /// Like `Sunday`.
+    pub day: String,
+    /// Local time, like `10:30`.
+    pub time: String,
+}
+
+/// LCR's Action and Interview List, which bishoprics work through weekly.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInterviewList {
+    #[serde(default)]
+    pub youth_interviews: Vec<YouthInterview>,
+    /// Endowed members without a current recommend.
+    #[serde(default)]
+    pub members_needing_recommends: Vec<ActionPerson>,
+    #[serde(default)]
+    pub children_approaching_baptism: Vec<ChildApproachingBaptism>,
+    /// Young men and men old enough to be ordained to a priesthood office who haven't been.
+    #[serde(default)]
+    pub priesthood_ordinations_due: Vec<PriesthoodOrdinationDue>,
+    #[serde(default)]
+    pub unendowed_adults: Vec<ActionPerson>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPerson {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: u64,
+    pub age: Option<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouthInterview {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: u64,
+    pub age: Option<u8>,
+    /// Bishop or bishopric counselor, as LCR labels it.
+    pub interview_type: Option<String>,
+    pub last_interview_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildApproachingBaptism {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: u64,
+    pub birth_date: Option<String>,
+}
+
+impl ChildApproachingBaptism {
+    pub fn birth_date(&self) -> Option<time::Date> {
+        self.birth_date.as_deref().and_then(parse_date)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriesthoodOrdinationDue {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: u64,
+    pub age: Option<u8>,
+    /// The office they're eligible for, like `Deacon` or `Elder`.
+    pub office: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ActionCategory {
+    YouthInterview,
+    RecommendNeeded,
+    ApproachingBaptism,
+    PriesthoodOrdination,
+    NotEndowed,
+}
+
+impl Display for ActionCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::YouthInterview => write!(f, "Youth interview"),
+            Self::RecommendNeeded => write!(f, "Recommend needed"),
+            Self::ApproachingBaptism => write!(f, "Approaching baptism"),
+            Self::PriesthoodOrdination => write!(f, "Priesthood ordination"),
+            Self::NotEndowed => write!(f, "Not endowed"),
         }
+    }
+}
 
-        if let Some(assignments) = &self.assignments {
-            for assignment in assignments {
-                let is_female = females_by_id
-                    .get(&assignment.legacy_cmis_id)
-                    .unwrap_or(&true);
-                if !only_females || *is_female {
-                    set.insert(assignment.name.to_string());
-                }
-            }
+/// One entry in the consolidated to-do list built from every category.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionItem {
+    pub category: ActionCategory,
+    pub name: String,
+    pub legacy_cmis_id: u64,
+    pub detail: Option<String>,
+}
+
+impl ActionInterviewList {
+    /// Every item in every category, grouped by category and then sorted by name.
+    pub fn to_do(&self) -> Vec<ActionItem> {
+        let item = |category, name: &String, legacy_cmis_id, detail| ActionItem {
+            category,
+            name: name.clone(),
+            legacy_cmis_id,
+            detail,
+        };
+        let age = |age: Option<u8>| age.map(|a| format!("Age {}", a));
+
+        let mut items: Vec<_> = self
+            .youth_interviews
+            .iter()
+            .map(|y| {
+                let detail = [
+                    y.interview_type.clone(),
+                    y.last_interview_date
+                        .as_deref()
+                        .and_then(parse_date)
+                        .map(|d| format!("last interviewed {}", d)),
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>();
+                item(
+                    ActionCategory::YouthInterview,
+                    &y.name,
+                    y.legacy_cmis_id,
+                    (!detail.is_empty()).then(|| detail.join(", ")),
+                )
+            })
+            .chain(self.members_needing_recommends.iter().map(|p| {
+                item(
+                    ActionCategory::RecommendNeeded,
+                    &p.name,
+                    p.legacy_cmis_id,
+                    age(p.age),
+                )
+            }))
+            .chain(self.children_approaching_baptism.iter().map(|c| {
+                item(
+                    ActionCategory::ApproachingBaptism,
+                    &c.name,
+                    c.legacy_cmis_id,
+                    c.birth_date().map(|d| format!("Born {}", d)),
+                )
+            }))
+            .chain(self.priesthood_ordinations_due.iter().map(|p| {
+                item(
+                    ActionCategory::PriesthoodOrdination,
+                    &p.name,
+                    p.legacy_cmis_id,
+                    p.office.clone(),
+                )
+            }))
+            .chain(self.unendowed_adults.iter().map(|p| {
+                item(
+                    ActionCategory::NotEndowed,
+                    &p.name,
+                    p.legacy_cmis_id,
+                    age(p.age),
+                )
+            }))
+            .collect();
+
+        items.sort_by(|a, b| (a.category, &a.name).cmp(&(b.category, &b.name)));
+        items
+    }
+}
+
+/// One month of weekly sacrament meeting attendance counts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SacramentAttendanceMonth {
+    #[serde(default)]
+    pub weeks: Vec<SacramentAttendanceWeek>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SacramentAttendanceWeek {
+    pub date: String,
+    /// Missing for weeks nobody has entered yet.
+    #[serde(alias = "value")]
+    pub count: Option<u32>,
+}
+
+impl SacramentAttendanceWeek {
+    pub fn date(&self) -> Option<time::Date> {
+        parse_date(&self.date)
+    }
+}
+
+/// A row from LCR's temple recommend status report.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendStatus {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: u64,
+    #[serde(alias = "type")]
+    pub recommend_type: Option<RecommendType>,
+    pub expiration_date: Option<String>,
+}
+
+impl RecommendStatus {
+    pub fn expiration_date(&self) -> Option<time::Date> {
+        self.expiration_date.as_deref().and_then(parse_date)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RecommendType {
+    Standard,
+    LimitedUse,
+    #[serde(other)]
+    Other,
+}
+
+impl Display for RecommendType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard => write!(f, "Standard"),
+            Self::LimitedUse => write!(f, "Limited use"),
+            Self::Other => write!(f, "Other"),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
+/// One month of LCR's birthday list report.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct MinisteringCompanionship {
-    name: String,
-    legacy_cmis_id: u64,
+pub struct BirthdayMonth {
+    pub month_int: u8,
+    #[serde(default)]
+    pub birthdays: Vec<Birthday>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct MinisteringAssignment {
-    name: String,
-    legacy_cmis_id: u64,
+pub struct Birthday {
+    pub name: String,
+    #[serde(alias = "id")]
+    pub legacy_cmis_id: Option<u64>,
+    pub birth_date: Option<String>,
+    /// The age they're turning, as of the report.
+    pub age: Option<u8>,
+}
+
+impl Birthday {
+    pub fn birth_date(&self) -> Option<time::Date> {
+        self.birth_date.as_deref().and_then(parse_date)
+    }
+}
+
+/// An organization from the "Callings by Organization" view, like the Bishopric or Primary.
+/// Sub-organizations such as classes and presidencies are nested in `children`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Organization {
+    pub sub_org_id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub children: Vec<Organization>,
+    #[serde(default)]
+    pub callings: Vec<Calling>,
+}
+
+impl Organization {
+    /// Every calling in this organization and its sub-organizations, along with the
+    /// sub-organization it's in.
+    pub fn all_callings(&self) -> Vec<(&Organization, &Calling)> {
+        let mut result: Vec<_> = self.callings.iter().map(|c| (self, c)).collect();
+        for child in &self.children {
+            result.extend(child.all_callings());
+        }
+        result
+    }
+
+    pub fn vacancies(&self) -> Vec<(&Organization, &Calling)> {
+        self.all_callings()
+            .into_iter()
+            .filter(|(_, c)| c.is_vacant())
+            .collect()
+    }
+}
+
+/// A position in an organization and who holds it, if anyone. Positions that allow several
+/// people, like teachers, show up once per person.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Calling {
+    pub position: String,
+    pub position_type_id: Option<u64>,
+    pub member_name: Option<String>,
+    #[serde(alias = "memberId")]
+    pub legacy_cmis_id: Option<u64>,
+    #[serde(alias = "activeDate")]
+    pub sustained_date: Option<String>,
+    pub set_apart_date: Option<String>,
+}
+
+impl Calling {
+    pub fn is_vacant(&self) -> bool {
+        self.legacy_cmis_id.is_none() && self.member_name.is_none()
+    }
+
+    pub fn sustained_date(&self) -> Option<time::Date> {
+        self.sustained_date.as_deref().and_then(parse_date)
+    }
+
+    pub fn set_apart_date(&self) -> Option<time::Date> {
+        self.set_apart_date.as_deref().and_then(parse_date)
+    }
+}
+
+#[cfg(test)]
+impl MemberListPerson {
+    pub(crate) fn test(id: u64, age: u8, sex: &str, household: &str) -> Self {
+        Self {
+            address: None,
+            age,
+            convert: false,
+            email: None,
+            phone_number: None,
+            sex: sex.to_string(),
+            legacy_cmis_id: id,
+            birth_date: None,
+            name_given_preferred_local: format!("Given{}", id),
+            name_family_preferred_local: "Family".to_string(),
+            name_list_preferred_local: format!("Family, Given{}", id),
+            household_uuid: Some(household.to_string()),
+            household_role: None,
+            household_email: None,
+            household_phone_number: None,
+            contact: ContactInfo::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn person(id: u64, age: u8, household: &str, role: HouseholdRole) -> MemberListPerson {
+        let mut person = MemberListPerson::test(id, age, "M", household);
+        person.household_role = Some(role);
+        person
+    }
+
+    #[test]
+    fn test_group_households() {
+        let mut lone = person(5, 30, "", HouseholdRole::Head);
+        lone.household_uuid = None;
+
+        let households = Household::group(
+            vec![
+                person(1, 10, "a", HouseholdRole::Other),
+                person(2, 40, "a", HouseholdRole::Spouse),
+                person(3, 42, "a", HouseholdRole::Head),
+                person(4, 25, "b", HouseholdRole::Other),
+                lone,
+                person(6, 12, "a", HouseholdRole::Other),
+            ],
+            1,
+        );
+
+        assert_eq!(households.len(), 3);
+        assert_eq!(households[0].uuid, "a");
+        assert_eq!(households[0].head.legacy_cmis_id, 3);
+        assert_eq!(households[0].spouse.as_ref().unwrap().legacy_cmis_id, 2);
+        let children: Vec<_> = households[0]
+            .children
+            .iter()
+            .map(|c| c.legacy_cmis_id)
+            .collect();
+        assert_eq!(children, vec![6, 1]);
+
+        // No head marked, so the oldest member is used.
+        assert_eq!(households[1].head.legacy_cmis_id, 4);
+        assert!(households[1].spouse.is_none());
+
+        assert_eq!(households[2].uuid, "5");
+        assert_eq!(households[2].members().count(), 1);
+    }
+
+    #[test]
+    fn test_household_do_not_contact() {
+        let mut head = person(1, 40, "a", HouseholdRole::Head);
+        head.household_email = Some("family@example.com".to_string());
+        let mut spouse = person(2, 40, "a", HouseholdRole::Spouse);
+        // Detected from the raw fields, without needing normalize_contact first.
+        spouse.email = Some("DNC spouse@example.com".to_string());
+
+        let households = Household::group(vec![head.clone(), spouse], 1);
+        assert!(households[0].contact.do_not_contact);
+        assert_eq!(households[0].contact.email, None);
+        // Plaintext output uses Debug, which mustn't show the raw fields either.
+        let debug = format!("{:?}", households);
+        assert!(!debug.contains("spouse@example.com"));
+        assert!(!debug.contains("family@example.com"));
+
+        let households = Household::group(vec![head], 1);
+        assert!(!households[0].contact.do_not_contact);
+        assert_eq!(
+            households[0].contact.email.as_ref().unwrap().as_str(),
+            "family@example.com"
+        );
+    }
+
+    #[test]
+    fn test_calling_code_from_address() {
+        let mut person = person(1, 40, "a", HouseholdRole::Head);
+        person.phone_number = Some("81 1234 5678".to_string());
+        person.address = Some(Address::from(RawAddress {
+            address_lines: vec![
+                "222 Avenida Constitucion".to_string(),
+                "Monterrey, NL 64000".to_string(),
+                "Mexico".to_string(),
+            ],
+        }));
+        person.normalize_contact(1);
+        assert_eq!(
+            person.contact.phone_number.as_ref().unwrap().as_str(),
+            "+528112345678"
+        );
+
+        person.phone_number = Some("020 7946 0958".to_string());
+        person.address = Some(Address::from(RawAddress {
+            address_lines: vec![
+                "10 Downing Street".to_string(),
+                "London SW1A 2AA".to_string(),
+                "United Kingdom".to_string(),
+            ],
+        }));
+        person.normalize_contact(1);
+        assert_eq!(
+            person.contact.phone_number.as_ref().unwrap().as_str(),
+            "+442079460958"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_ministering() {
+        let json = r#"{
+            "elders": [{
+                "districts": [{
+                    "districtName": "District 1",
+                    "supervisorName": "Smith, John",
+                    "supervisorLegacyCmisId": 10,
+                    "companionships": [{
+                        "id": "c1",
+                        "ministers": [{"name": "Doe, Jim", "legacyCmisId": 1}],
+                        "assignments": [{"name": "Roe Family", "legacyCmisId": 2}]
+                    }]
+                }],
+                "companionships": [{
+                    "ministers": [{"name": "Poe, Sam", "legacyCmisId": 3}],
+                    "assignments": null
+                }]
+            }]
+        }"#;
+
+        let ministering: Ministering = serde_json::from_str(json).unwrap();
+        let quorum = &ministering.quorums[0];
+        assert_eq!(quorum.districts[0].supervisor_legacy_cmis_id, Some(10));
+
+        let companionships: Vec<_> = quorum.all_companionships().collect();
+        assert_eq!(companionships.len(), 2);
+        assert_eq!(companionships[0].assignments()[0].legacy_cmis_id, 2);
+        assert!(companionships[1].assignments().is_empty());
+
+        assert_eq!(ministering.ids(), HashSet::from([1, 2, 3]));
+        assert_eq!(companionships[1].ids(), HashSet::from([3]));
+        assert_eq!(ministering.names_by_id()[&3], "Poe, Sam");
+
+        let relief_society: Ministering =
+            serde_json::from_str(r#"{"reliefSociety": [{"companionships": []}]}"#).unwrap();
+        assert_eq!(relief_society.quorums.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_ministering_organization() {
+        for org in MinisteringOrganization::ALL {
+            assert_eq!(org.query_type().parse(), Ok(org));
+        }
+        assert_eq!(
+            "relief-society".parse(),
+            Ok(MinisteringOrganization::ReliefSociety)
+        );
+        assert!("primary".parse::<MinisteringOrganization>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_callings() {
+        let orgs: Vec<Organization> = serde_json::from_str(
+            r#"[{"subOrgId": 1, "name": "Primary",
+                 "callings": [{"position": "Primary President", "memberName": "Doe, Jane",
+                               "memberId": 5, "activeDate": "20230115", "setApartDate": "2023-01-22"}],
+                 "children": [{"subOrgId": 2, "name": "CTR 7",
+                               "callings": [{"position": "Primary Teacher"}]}]}]"#,
+        )
+        .unwrap();
+
+        let callings = orgs[0].all_callings();
+        assert_eq!(callings.len(), 2);
+        assert_eq!(callings[0].1.legacy_cmis_id, Some(5));
+        assert_eq!(
+            callings[0].1.sustained_date(),
+            Some(time::macros::date!(2023 - 01 - 15))
+        );
+        assert_eq!(
+            callings[0].1.set_apart_date(),
+            Some(time::macros::date!(2023 - 01 - 22))
+        );
+
+        let vacancies = orgs[0].vacancies();
+        assert_eq!(vacancies.len(), 1);
+        assert_eq!(vacancies[0].0.name, "CTR 7");
+    }
+
+    #[test]
+    fn test_action_interview_list_to_do() {
+        let list: ActionInterviewList = serde_json::from_str(
+            r#"{
+                "youthInterviews": [{"name": "Youth, B", "id": 2, "age": 14,
+                                     "interviewType": "Bishop", "lastInterviewDate": "20240301"}],
+                "childrenApproachingBaptism": [{"name": "Child, C", "id": 3, "birthDate": "20180704"}],
+                "unendowedAdults": [{"name": "Adult, Z", "id": 5, "age": 30},
+                                    {"name": "Adult, A", "id": 4}]
+            }"#,
+        )
+        .unwrap();
+
+        let to_do = list.to_do();
+        let names: Vec<_> = to_do.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["Youth, B", "Child, C", "Adult, A", "Adult, Z"]);
+        assert_eq!(
+            to_do[0].detail.as_deref(),
+            Some("Bishop, last interviewed 2024-03-01")
+        );
+        assert_eq!(to_do[1].detail.as_deref(), Some("Born 2018-07-04"));
+        assert_eq!(to_do[2].detail, None);
+    }
+
+    #[test]
+    fn test_deserialize_units() {
+        let units: UserUnits = serde_json::from_str(
+            r#"{
+                "homeUnitNumber": 111,
+                "units": [
+                    {"unitNumber": 100, "unitName": "Provo Stake", "unitType": "STAKE"},
+                    {"unitNumber": 111, "unitName": "First Ward", "unitType": "WARD",
+                     "parentUnitNumber": 100, "parentUnitName": "Provo Stake"},
+                    {"unitNumber": 222, "name": "Spanish Branch", "unitType": "Branch"},
+                    {"unitNumber": 333, "name": "Mission"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(units.home_unit_number, 111);
+        assert_eq!(units.units[1].name, "First Ward");
+        assert_eq!(units.units[1].parent_unit_number, Some(100));
+        assert_eq!(units.units[2].name, "Spanish Branch");
+
+        let wards_and_branches: Vec<_> = units
+            .units
+            .iter()
+            .filter(|u| u.is_ward_or_branch())
+            .map(|u| u.unit_number)
+            .collect();
+        assert_eq!(wards_and_branches, vec![111, 222]);
+
+        let details: UnitDetails = serde_json::from_str(
+            r#"{
+                "unitNumber": 111,
+                "unitName": "First Ward",
+                "unitType": "WARD",
+                "address": {"addressLines": ["10 Downing Street", "London SW1A 2AA", "United Kingdom"]},
+                "meetingTimes": [{"day": "Sunday", "time": "10:30"}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(details.name, "First Ward");
+        assert_eq!(
+            details.address.unwrap().parsed.country.as_deref(),
+            Some("United Kingdom")
+        );
+        assert_eq!(details.meeting_times[0].time, "10:30");
+
+        let details: UnitDetails =
+            serde_json::from_str(r#"{"unitNumber": 222, "name": "Spanish Branch"}"#).unwrap();
+        assert!(details.address.is_none());
+        assert!(details.meeting_times.is_empty());
+    }
 }
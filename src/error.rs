@@ -26,4 +26,16 @@ pub enum Error {
 
     #[error("Error while doing IO: {0}")]
     Io(#[from] io::Error),
+
+    #[error("Error reading or writing JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Error generating PDF: {0}")]
+    Pdf(String),
+
+    #[error("Error reading CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
@@ -0,0 +1,177 @@
+//! Minimal iCalendar (RFC 5545) writer for exporting dates to calendar apps.
+
+use time::{Date, Duration, PrimitiveDateTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Yearly,
+    /// Every year on the last day of February, for events starting on February 29th that would
+    /// otherwise only recur in leap years.
+    YearlyLastDayOfFebruary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    AllDay(Date),
+    /// In the calendar app's local time zone.
+    At {
+        start: PrimitiveDateTime,
+        duration: Duration,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// Must be unique and stable across exports so re-importing updates events instead of
+    /// duplicating them.
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub time: EventTime,
+    pub recurrence: Option<Recurrence>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub name: String,
+    pub events: Vec<Event>,
+}
+
+fn format_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    )
+}
+
+fn format_date_time(date_time: PrimitiveDateTime) -> String {
+    format!(
+        "{}T{:02}{:02}{:02}",
+        format_date(date_time.date()),
+        date_time.hour(),
+        date_time.minute(),
+        date_time.second()
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Writes a content line, folding it at 75 octets as the spec requires.
+fn write_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+impl Calendar {
+    /// `stamp` is recorded as when each event was created, and should be in UTC.
+    pub fn to_ics(&self, stamp: PrimitiveDateTime) -> String {
+        let mut out = String::new();
+        write_line(&mut out, "BEGIN:VCALENDAR");
+        write_line(&mut out, "VERSION:2.0");
+        write_line(&mut out, "PRODID:-//lcr//EN");
+        write_line(&mut out, &format!("X-WR-CALNAME:{}", escape(&self.name)));
+
+        for event in &self.events {
+            write_line(&mut out, "BEGIN:VEVENT");
+            write_line(&mut out, &format!("UID:{}", event.uid));
+            write_line(&mut out, &format!("DTSTAMP:{}Z", format_date_time(stamp)));
+            match event.time {
+                EventTime::AllDay(date) => {
+                    write_line(
+                        &mut out,
+                        &format!("DTSTART;VALUE=DATE:{}", format_date(date)),
+                    );
+                }
+                EventTime::At { start, duration } => {
+                    write_line(&mut out, &format!("DTSTART:{}", format_date_time(start)));
+                    write_line(
+                        &mut out,
+                        &format!("DTEND:{}", format_date_time(start + duration)),
+                    );
+                }
+            }
+            if let Some(recurrence) = event.recurrence {
+                let rule = match recurrence {
+                    Recurrence::Yearly => "FREQ=YEARLY",
+                    Recurrence::YearlyLastDayOfFebruary => "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=-1",
+                };
+                write_line(&mut out, &format!("RRULE:{}", rule));
+            }
+            write_line(&mut out, &format!("SUMMARY:{}", escape(&event.summary)));
+            if let Some(description) = &event.description {
+                write_line(&mut out, &format!("DESCRIPTION:{}", escape(description)));
+            }
+            write_line(&mut out, "END:VEVENT");
+        }
+
+        write_line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn test_to_ics() {
+        let calendar = Calendar {
+            name: "Birthdays".to_string(),
+            events: vec![Event {
+                uid: "birthday-1@lcr".to_string(),
+                summary: "Doe, Jane".to_string(),
+                description: None,
+                time: EventTime::AllDay(date!(2000 - 02 - 29)),
+                recurrence: Some(Recurrence::Yearly),
+            }],
+        };
+
+        let ics = calendar.to_ics(datetime!(2025-01-02 03:04:05));
+        assert!(ics.contains("DTSTAMP:20250102T030405Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20000229\r\n"));
+        assert!(ics.contains("RRULE:FREQ=YEARLY\r\n"));
+        assert!(ics.contains("SUMMARY:Doe\\, Jane\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+
+        let timed = Calendar {
+            name: "Interviews".to_string(),
+            events: vec![Event {
+                uid: "interview-1@lcr".to_string(),
+                summary: "Interview".to_string(),
+                description: Some("Bishop\nOffice".to_string()),
+                time: EventTime::At {
+                    start: datetime!(2025-07-06 10:00),
+                    duration: Duration::minutes(15),
+                },
+                recurrence: None,
+            }],
+        };
+        let ics = timed.to_ics(datetime!(2025-01-02 03:04:05));
+        assert!(ics.contains("DTSTART:20250706T100000\r\n"));
+        assert!(ics.contains("DTEND:20250706T101500\r\n"));
+        assert!(ics.contains("DESCRIPTION:Bishop\\nOffice\r\n"));
+
+        let mut folded = String::new();
+        write_line(&mut folded, &"x".repeat(100));
+        assert_eq!(
+            folded,
+            format!("{}\r\n {}\r\n", "x".repeat(75), "x".repeat(25))
+        );
+    }
+}
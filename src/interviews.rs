@@ -0,0 +1,282 @@
+//! Scheduling youth interviews, computed from birth dates in the member list.
+//!
+//! Youth ages 12 through 17 meet with the bishop around their birthday and with a counselor in
+//! the bishopric about six months later.
+
+use std::fmt::{self, Display};
+use std::io::Read;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use time::macros::format_description;
+use time::{Date, Duration, Month, PrimitiveDateTime};
+
+use crate::birthdays::in_year;
+use crate::data::MemberListPerson;
+use crate::error::Error;
+use crate::ics::{Calendar, Event, EventTime};
+
+const YOUTH_AGES: std::ops::RangeInclusive<i32> = 12..=17;
+
+const SLOT_FORMAT: &[time::format_description::BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Interviewer {
+    Bishop,
+    Counselor,
+}
+
+impl Display for Interviewer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bishop => write!(f, "Bishop"),
+            Self::Counselor => write!(f, "Counselor"),
+        }
+    }
+}
+
+impl FromStr for Interviewer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bishop" => Ok(Self::Bishop),
+            "counselor" | "counsellor" => Ok(Self::Counselor),
+            _ => Err(format!("Unknown interviewer: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DueInterview<'a> {
+    pub member: &'a MemberListPerson,
+    pub interviewer: Interviewer,
+    pub due: Date,
+    /// Age at the birthday the interview goes with.
+    pub age: i32,
+}
+
+fn six_months_after(date: Date) -> Date {
+    let month_index = u8::from(date.month()) + 6;
+    let (year, month) = if month_index > 12 {
+        (date.year() + 1, month_index - 12)
+    } else {
+        (date.year(), month_index)
+    };
+    let month = Month::try_from(month).expect("Month is between 1 and 12");
+    let day = date.day().min(month.length(year));
+    Date::from_calendar_date(year, month, day).expect("Day was clamped to the month")
+}
+
+/// Interviews due between `start` and `end` inclusive, soonest first. Members without a birth
+/// date are skipped.
+pub fn due_interviews(
+    members: &[MemberListPerson],
+    start: Date,
+    end: Date,
+) -> Vec<DueInterview<'_>> {
+    let mut due = Vec::new();
+    for member in members {
+        let Some(birth_date) = member.birth_date() else {
+            continue;
+        };
+
+        // Counselor interviews fall in the year after the birthday they go with.
+        for year in start.year() - 1..=end.year() {
+            let age = year - birth_date.year();
+            if !YOUTH_AGES.contains(&age) {
+                continue;
+            }
+
+            let birthday = in_year(birth_date, year);
+            for (interviewer, date) in [
+                (Interviewer::Bishop, birthday),
+                (Interviewer::Counselor, six_months_after(birthday)),
+            ] {
+                if (start..=end).contains(&date) {
+                    due.push(DueInterview {
+                        member,
+                        interviewer,
+                        due: date,
+                        age,
+                    });
+                }
+            }
+        }
+    }
+
+    due.sort_by(|a, b| {
+        (a.due, &a.member.name_list_preferred_local)
+            .cmp(&(b.due, &b.member.name_list_preferred_local))
+    });
+    due
+}
+
+/// A time someone in the bishopric is available for an interview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    pub start: PrimitiveDateTime,
+    pub interviewer: Interviewer,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlotRow {
+    start: String,
+    interviewer: String,
+}
+
+/// Reads available slots from a CSV with `start` (`YYYY-MM-DD HH:MM`) and `interviewer`
+/// (`bishop` or `counselor`) columns.
+///
+/// # Errors
+/// Malformed CSV, times that don't parse, or unknown interviewers.
+pub fn read_slots<R: Read>(reader: R) -> Result<Vec<Slot>, Error> {
+    let mut slots = Vec::new();
+    for row in csv::Reader::from_reader(reader).deserialize() {
+        let row: SlotRow = row?;
+        let start = PrimitiveDateTime::parse(row.start.trim(), SLOT_FORMAT).map_err(|_| {
+            Error::InvalidInput(format!("Not a YYYY-MM-DD HH:MM time: {}", row.start))
+        })?;
+        slots.push(Slot {
+            start,
+            interviewer: row.interviewer.parse().map_err(Error::InvalidInput)?,
+        });
+    }
+    Ok(slots)
+}
+
+/// A proposed interview, or one that couldn't be fit into any slot if `start` is empty.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Appointment {
+    pub name: String,
+    pub legacy_cmis_id: u64,
+    pub interviewer: Interviewer,
+    pub age: i32,
+    pub due: String,
+    pub start: Option<String>,
+    #[serde(skip)]
+    pub slot: Option<Slot>,
+}
+
+/// Gives each due interview the earliest open slot with the right interviewer, handing out slots
+/// in order of due date.
+pub fn schedule(due: &[DueInterview], slots: &[Slot]) -> Vec<Appointment> {
+    let mut open: Vec<_> = slots.to_vec();
+    open.sort_by_key(|s| s.start);
+
+    due.iter()
+        .map(|interview| {
+            let slot = open
+                .iter()
+                .position(|s| s.interviewer == interview.interviewer)
+                .map(|i| open.remove(i));
+
+            Appointment {
+                name: interview.member.name_list_preferred_local.clone(),
+                legacy_cmis_id: interview.member.legacy_cmis_id,
+                interviewer: interview.interviewer,
+                age: interview.age,
+                due: interview.due.to_string(),
+                start: slot.and_then(|s| s.start.format(SLOT_FORMAT).ok()),
+                slot,
+            }
+        })
+        .collect()
+}
+
+/// A calendar event for every scheduled appointment.
+pub fn calendar(appointments: &[Appointment], length: Duration) -> Calendar {
+    let events = appointments
+        .iter()
+        .filter_map(|a| {
+            let slot = a.slot?;
+            Some(Event {
+                uid: format!(
+                    "interview-{}-{}@lcr",
+                    a.legacy_cmis_id,
+                    slot.start.assume_utc().unix_timestamp()
+                ),
+                summary: format!("{} interview: {}", a.interviewer, a.name),
+                description: Some(format!("Youth interview, age {}, due {}", a.age, a.due)),
+                time: EventTime::At {
+                    start: slot.start,
+                    duration: length,
+                },
+                recurrence: None,
+            })
+        })
+        .collect();
+
+    Calendar {
+        name: "Youth Interviews".to_string(),
+        events,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn youth(id: u64, birth_date: &str) -> MemberListPerson {
+        let mut member = MemberListPerson::test(id, 14, "F", "a");
+        member.birth_date = Some(birth_date.to_string());
+        member
+    }
+
+    #[test]
+    fn test_due_interviews() {
+        let members = vec![
+            youth(1, "20110815"),
+            youth(2, "20100228"),
+            youth(3, "20200801"),
+            youth(4, "20070810"),
+            MemberListPerson::test(5, 14, "M", "b"),
+        ];
+
+        let due = due_interviews(&members, date!(2025 - 08 - 01), date!(2025 - 08 - 31));
+        let found: Vec<_> = due
+            .iter()
+            .map(|d| (d.member.legacy_cmis_id, d.interviewer, d.due, d.age))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, Interviewer::Bishop, date!(2025 - 08 - 15), 14),
+                (2, Interviewer::Counselor, date!(2025 - 08 - 28), 15),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        let members = vec![
+            youth(1, "20110815"),
+            youth(2, "20100228"),
+            youth(3, "20120820"),
+        ];
+        let due = due_interviews(&members, date!(2025 - 08 - 01), date!(2025 - 08 - 31));
+        let slots = read_slots(
+            "start,interviewer\n2025-08-17 10:00,bishop\n2025-08-17 09:30,counselor\n".as_bytes(),
+        )
+        .unwrap();
+
+        let appointments = schedule(&due, &slots);
+        assert_eq!(appointments.len(), 3);
+        assert_eq!(appointments[0].legacy_cmis_id, 1);
+        assert_eq!(appointments[0].start.as_deref(), Some("2025-08-17 10:00"));
+        // The only bishop slot went to the first one due.
+        assert_eq!(appointments[1].legacy_cmis_id, 3);
+        assert_eq!(appointments[1].start, None);
+        assert_eq!(
+            appointments[2].slot.map(|s| s.start),
+            Some(datetime!(2025-08-17 09:30))
+        );
+
+        let calendar = calendar(&appointments, Duration::minutes(15));
+        assert_eq!(calendar.events.len(), 2);
+    }
+}
@@ -1,3 +1,18 @@
+pub mod address;
+pub mod advancement;
+pub mod attendance;
+pub mod birthdays;
+pub mod callings;
 pub mod client;
+pub mod contact;
 pub mod data;
 pub mod error;
+pub mod ics;
+pub mod interviews;
+pub mod ministering;
+pub mod names;
+pub mod ordinances;
+pub mod recommends;
+pub mod sacrament;
+pub mod search;
+pub mod stake;
@@ -1,11 +1,37 @@
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::{Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
 use lcr::client::ClientOptions;
-use lcr::{client::Client, data::MemberListPerson};
-use std::collections::HashMap;
+use lcr::ministering::{
+    self,
+    cards::{self, PaperSize},
+    diff::{self, PeopleDiff},
+    gaps::{self, GapReport},
+    graph,
+    history::{self, CompanionshipSummary, HistoryDiff, Snapshot},
+    proposal::{self, Constraints},
+};
+use lcr::{
+    advancement, attendance, birthdays,
+    callings::{self, CallingReport},
+    client::Client,
+    data::{
+        ClassAttendance, Household, MemberListPerson, MemberProfile, Ministering,
+        MinisteringOrganization, MovedInPerson, MovedOutPerson, Organization, Sex,
+    },
+    interviews,
+    ordinances::{self, OrdinanceSummary},
+    recommends,
+    sacrament::{self, TrendWeek},
+    search,
+    stake::{self, StakeResults, UnitLabel, UnitSummary},
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
-use time::OffsetDateTime;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset, Weekday};
 
 mod visual_directory;
 
@@ -17,6 +43,11 @@ struct Cli {
     /// Shows Chrome as non-headless
     shows_chrome: bool,
 
+    #[clap(long)]
+    /// Calling code used for phone numbers entered without one, unless the member's address
+    /// names a known country. Defaults to the country of the unit's address, or 1 if unknown
+    calling_code: Option<u16>,
+
     #[clap(subcommand)]
     command: Commands,
 }
@@ -44,14 +75,365 @@ enum Commands {
         output: OutputType,
     },
 
+    /// Print members grouped by household
+    Households {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+
+        /// Sort households by street address
+        #[arg(long)]
+        by_street: bool,
+    },
+
+    /// Print upcoming birthdays
+    Birthdays {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+
+        /// How many months ahead to look, starting with this month
+        #[arg(long, default_value_t = 1)]
+        months: u8,
+
+        /// Leave ages and birth years out, for sharing with class presidencies
+        #[arg(long)]
+        omit_age: bool,
+
+        /// Also write everyone's birthday, not just upcoming ones, to this iCalendar file as yearly
+        /// events
+        #[arg(long)]
+        ics: Option<PathBuf>,
+    },
+
+    /// Print temple recommends that have expired or expire soon, grouped by month
+    Recommends {
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+
+        /// Include recommends expiring through this many months from now
+        #[arg(long, default_value_t = 2)]
+        months: u8,
+    },
+
+    /// Print a to-do list from the Action and Interview List
+    Actions {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+    },
+
+    /// Schedule youth interviews due in a date range into available slots
+    Interviews {
+        /// Start of the range, as YYYY-MM-DD. Defaults to today.
+        #[arg(long, value_parser = parse_date_arg)]
+        from: Option<Date>,
+
+        /// How many days the range covers
+        #[arg(long, default_value_t = 31)]
+        days: i64,
+
+        /// CSV of available slots with start (YYYY-MM-DD HH:MM) and interviewer (bishop or
+        /// counselor) columns. Without it, interviews due are listed unscheduled.
+        #[arg(long)]
+        slots: Option<PathBuf>,
+
+        /// Minutes each interview takes on the calendar
+        #[arg(long, default_value_t = 15)]
+        minutes: i64,
+
+        /// Also write the scheduled interviews to this iCalendar file
+        #[arg(long)]
+        ics: Option<PathBuf>,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print children turning 8 and youth advancing in January, grouped by month
+    Milestones {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+
+        /// Include milestones through this many months from now
+        #[arg(long, default_value_t = 3)]
+        months: u8,
+    },
+
+    /// Print the units you can access, or details about one of them
+    Units {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+
+        /// Print the address and meeting times of this unit instead
+        #[arg(long)]
+        details: Option<String>,
+    },
+
+    /// Print callings by organization
+    Callings {
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+
+        /// Only print positions nobody holds
+        #[arg(long)]
+        vacant: bool,
+    },
+
+    /// Print vacant callings, people with several callings, adults without one and tenure in
+    /// calling
+    CallingReport {
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+    },
+
+    /// Find members by name, ignoring accents and small misspellings
+    Find {
+        query: String,
+
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+
+        /// Also fetch and print each match's full member profile
+        #[arg(long)]
+        profile: bool,
+    },
+
     /// Output visual members list
     VisualMembers,
 
     /// Print report
     Report,
 
+    /// Print ordinance counts for adults, unendowed adults by age and unordained men
+    Ordinances,
+
+    /// Show or record class and quorum attendance
+    Attendance {
+        #[command(subcommand)]
+        command: AttendanceCommands,
+    },
+
+    /// Show or record sacrament meeting attendance counts
+    Sacrament {
+        #[command(subcommand)]
+        command: SacramentCommands,
+    },
+
+    /// Combine members, moves and the summary report across several units
+    Stake {
+        /// Comma separated unit numbers. Defaults to every ward and branch you can access.
+        #[arg(long, value_delimiter = ',')]
+        units: Vec<String>,
+
+        #[command(subcommand)]
+        command: StakeCommands,
+    },
+
     /// Get members involved with ministering
-    Ministering,
+    Ministering {
+        #[command(subcommand)]
+        command: MinisteringCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AttendanceCommands {
+    /// Print who attended each Sunday
+    Show {
+        /// The class or quorum's `subOrgId` from the `callings` command
+        #[arg(long)]
+        org: u64,
+
+        /// First Sunday to include, as YYYY-MM-DD
+        #[arg(long, value_parser = parse_date_arg)]
+        from: Date,
+
+        /// Last Sunday to include, as YYYY-MM-DD. Defaults to today.
+        #[arg(long, value_parser = parse_date_arg)]
+        to: Option<Date>,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Record attendance from a CSV file, or from a roll-call file with one name per line
+    Import {
+        /// The class or quorum's `subOrgId` from the `callings` command
+        #[arg(long)]
+        org: u64,
+
+        /// The Sunday a roll-call file is for, as YYYY-MM-DD. Not needed for CSV files, which
+        /// have a date column.
+        #[arg(long, value_parser = parse_date_arg)]
+        date: Option<Date>,
+
+        /// Print the changes without submitting them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// A `.csv` file with date, legacyCmisId or name, and attended columns, or a roll-call
+        /// file
+        path: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum StakeCommands {
+    /// Print every unit's members with a unit column
+    Members {
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print every unit's recent move-ins and move-outs
+    Moves {
+        #[arg(long, default_value_t = 2)]
+        months: u8,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print member and move counts per unit with stake totals
+    Report {
+        #[arg(long, default_value_t = 2)]
+        months: u8,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SacramentCommands {
+    /// Print weekly counts with rolling averages and the same week last year
+    Trend {
+        /// How many of the most recent weeks to print
+        #[arg(long, default_value_t = 12)]
+        weeks: usize,
+
+        /// How many weeks the rolling average covers
+        #[arg(long, default_value_t = 4)]
+        window: usize,
+
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+    },
+
+    /// Submit the count for a Sunday
+    Record {
+        /// The Sunday, as YYYY-MM-DD. Defaults to the most recent Sunday.
+        #[arg(long, value_parser = parse_date_arg)]
+        date: Option<Date>,
+
+        count: u32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum MinisteringCommands {
+    /// Print companionships and their assignments
+    List {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print people in one organization's ministering but not the other's
+    Diff {
+        #[arg(long, default_value = "eq")]
+        from: MinisteringOrganization,
+
+        #[arg(long, default_value = "rs")]
+        to: MinisteringOrganization,
+
+        /// Only compare people of this sex
+        #[arg(long)]
+        sex: Option<Sex>,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print households without ministers, eligible ministers without a companionship and
+    /// companionships without assignments
+    Gaps {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Export every assignment of every organization
+    Export {
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Print ministering as an org chart graph
+    Graph {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        #[arg(long, short, value_enum)]
+        format: GraphFormat,
+    },
+
+    /// Write a PDF of cards, one per companionship, listing their families' contact info
+    Cards {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        /// Letter or A4
+        #[arg(long, default_value = "letter")]
+        paper: PaperSize,
+
+        /// Leave family photos off the cards
+        #[arg(long)]
+        no_photos: bool,
+
+        /// Where to write the PDF
+        path: PathBuf,
+    },
+
+    /// Propose new companionships and assignments after members move in and out
+    Propose {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        /// Most families to give one companionship
+        #[arg(long, default_value_t = Constraints::default().max_families_per_companionship)]
+        max_families: usize,
+
+        /// How many months of move-ins and move-outs to consider
+        #[arg(long, default_value_t = 6)]
+        months: u8,
+
+        #[arg(long, short, value_enum)]
+        output: TabularOutputType,
+    },
+
+    /// Save the current ministering assignments to a file to compare against later
+    Snapshot {
+        #[arg(long, default_value = "eq")]
+        org: MinisteringOrganization,
+
+        /// Where to save the snapshot
+        path: PathBuf,
+    },
+
+    /// Print what changed in ministering between two snapshots
+    History {
+        /// The older snapshot
+        before: PathBuf,
+
+        /// The newer snapshot
+        after: PathBuf,
+
+        #[arg(long, short, value_enum)]
+        output: OutputType,
+    },
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
@@ -60,21 +442,60 @@ enum OutputType {
     Json,
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+enum GraphFormat {
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+enum TabularOutputType {
+    Plaintext,
+    Json,
+    Csv,
+}
+
+fn parse_date_arg(s: &str) -> std::result::Result<Date, String> {
+    lcr::data::parse_date(s).ok_or_else(|| format!("Not a YYYY-MM-DD date: {}", s))
+}
+
+static LOCAL_OFFSET: OnceLock<UtcOffset> = OnceLock::new();
+
+/// The current time in the local time zone, or in UTC if that couldn't be determined.
+fn now() -> OffsetDateTime {
+    OffsetDateTime::now_utc().to_offset(LOCAL_OFFSET.get().copied().unwrap_or(UtcOffset::UTC))
+}
+
+fn main() -> Result<()> {
+    // `time` only looks up the local offset while the process has a single thread, so this has to
+    // happen before tokio starts its workers.
+    if let Ok(offset) = UtcOffset::current_local_offset() {
+        let _ = LOCAL_OFFSET.set(offset);
+    }
+    tokio::runtime::Runtime::new()?.block_on(run())
+}
+
+async fn run() -> Result<()> {
     let cli = Cli::parse();
 
     let username = &env::var("LCR_USERNAME").expect("LCR_USERNAME env var required");
     let password = &env::var("LCR_PASSWORD").expect("LCR_PASSWORD env var required");
-    let unit_number = &env::var("LCR_UNIT").expect("LCR_UNIT env var required");
+    // Without a unit number, log in first and use the user's home unit.
+    let unit_number = env::var("LCR_UNIT").ok();
     let mut client = Client::new_with_options(
         username,
         password,
-        unit_number,
+        unit_number.as_deref().unwrap_or_default(),
         ClientOptions {
             headless: !cli.shows_chrome,
+            default_calling_code: cli.calling_code,
         },
     );
+    if unit_number.is_none() {
+        client
+            .use_home_unit()
+            .context("LCR_UNIT isn't set and unable to find your home unit")?;
+    }
 
     match cli.command {
         Commands::Emails => {
@@ -109,44 +530,258 @@ async fn main() -> Result<()> {
                 OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &member_list)?,
             }
         }
-        Commands::Ministering => {
-            let male_ministering = client
-                .ministering_people(true, true)
-                .context("Unable to fetch male ministering people")?;
-
-            let female_ministering = client
-                .ministering_people(false, true)
-                .context("Unable to fetch female ministering people")?;
+        Commands::Households { output, by_street } => {
+            let mut households = client.households().context("Unable to fetch households")?;
+            if by_street {
+                households.sort_by_key(|h| h.street_sort_key());
+            }
+            match output {
+                OutputType::Plaintext => println!("{:#?}", households),
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &households)?,
+            }
+        }
+        Commands::Ministering {
+            command: MinisteringCommands::List { org, output },
+        } => {
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
 
-            println!(
-                "Women to remove from ministering:\n{}\n",
-                male_ministering
-                    .difference(&female_ministering)
-                    .join("\t\n")
+            match output {
+                TabularOutputType::Plaintext => print_ministering(&ministering),
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &ministering)?
+                }
+                TabularOutputType::Csv => {
+                    write_csv(ministering::assignment_rows(org, &ministering))?
+                }
+            }
+        }
+        Commands::Ministering {
+            command:
+                MinisteringCommands::Diff {
+                    from,
+                    to,
+                    sex,
+                    output,
+                },
+        } => {
+            let member_list = client
+                .member_list()
+                .context("Unable to fetch member list")?;
+            let from_ministering = client
+                .ministering(from)
+                .context("Unable to fetch ministering")?;
+            let to_ministering = client
+                .ministering(to)
+                .context("Unable to fetch ministering")?;
+            let diff = diff::diff_people(
+                (from, &from_ministering),
+                (to, &to_ministering),
+                &member_list,
+                sex,
             );
-            println!(
-                "Women to add to ministering:\n{}\n",
-                female_ministering
-                    .difference(&male_ministering)
-                    .join("\t\n")
+
+            match output {
+                TabularOutputType::Plaintext => print_ministering_diff(&diff),
+                TabularOutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &diff)?,
+                TabularOutputType::Csv => write_csv(diff.rows())?,
+            }
+        }
+        Commands::Ministering {
+            command: MinisteringCommands::Gaps { org, output },
+        } => {
+            let households = client.households().context("Unable to fetch households")?;
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
+            let other_org = MinisteringOrganization::ALL
+                .into_iter()
+                .find(|o| *o != org)
+                .expect("There are two ministering organizations");
+            let other = client
+                .ministering(other_org)
+                .context("Unable to fetch ministering")?;
+            let report = gaps::find_gaps(org, &ministering, &other, &households);
+
+            match output {
+                TabularOutputType::Plaintext => print_ministering_gaps(&report),
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &report)?
+                }
+                TabularOutputType::Csv => write_csv(report.rows())?,
+            }
+        }
+        Commands::Ministering {
+            command: MinisteringCommands::Export { output },
+        } => {
+            let mut all = Vec::new();
+            for org in MinisteringOrganization::ALL {
+                let ministering = client
+                    .ministering(org)
+                    .context("Unable to fetch ministering")?;
+                all.push((org, ministering));
+            }
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for (org, ministering) in &all {
+                        println!("{}:", org);
+                        print_ministering(ministering);
+                    }
+                }
+                TabularOutputType::Json => {
+                    // Sorted so exports from different days can be diffed.
+                    let by_org: BTreeMap<_, _> = all.iter().map(|(org, m)| (org, m)).collect();
+                    serde_json::to_writer_pretty(std::io::stdout(), &by_org)?
+                }
+                TabularOutputType::Csv => write_csv(
+                    all.iter()
+                        .flat_map(|(org, m)| ministering::assignment_rows(*org, m)),
+                )?,
+            }
+        }
+        Commands::Ministering {
+            command: MinisteringCommands::Graph { org, format },
+        } => {
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
+
+            match format {
+                GraphFormat::Dot => print!("{}", graph::to_dot(org, &ministering)),
+                GraphFormat::Mermaid => print!("{}", graph::to_mermaid(org, &ministering)),
+            }
+        }
+        Commands::Ministering {
+            command:
+                MinisteringCommands::Propose {
+                    org,
+                    max_families,
+                    months,
+                    output,
+                },
+        } => {
+            let households = client.households().context("Unable to fetch households")?;
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
+            let moved_in = client
+                .moved_in(months)
+                .context("Unable to fetch moved in list")?;
+            let moved_out = client
+                .moved_out(months)
+                .context("Unable to fetch moved out list")?;
+            let plan = proposal::propose(
+                org,
+                &ministering,
+                &households,
+                &moved_in,
+                &moved_out,
+                Constraints {
+                    max_families_per_companionship: max_families,
+                    ..Default::default()
+                },
             );
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for change in &plan.changes {
+                        println!(
+                            "{:?}\t{}\t{}\t{}",
+                            change.action,
+                            change.companionship.as_deref().unwrap_or("-"),
+                            change.name.as_deref().unwrap_or("-"),
+                            change.reason
+                        );
+                    }
+                }
+                TabularOutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &plan)?,
+                TabularOutputType::Csv => write_csv(&plan.changes)?,
+            }
+        }
+        Commands::Ministering {
+            command:
+                MinisteringCommands::Cards {
+                    org,
+                    paper,
+                    no_photos,
+                    path,
+                },
+        } => {
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
+            let households = client.households().context("Unable to fetch households")?;
+            let visual_members = if no_photos {
+                Vec::new()
+            } else {
+                client
+                    .visual_member_list()
+                    .context("Unable to fetch member photos")?
+            };
+            let cards = cards::build_cards(&ministering, &households, &visual_members);
+
+            let mut photos = HashMap::new();
+            for url in cards
+                .iter()
+                .flat_map(|c| &c.families)
+                .filter_map(|f| f.photo_url.as_ref())
+                .unique()
+            {
+                // A missing photo shouldn't keep the rest of the cards from printing.
+                match client.photo(url) {
+                    Ok(bytes) => {
+                        photos.insert(url.clone(), bytes);
+                    }
+                    Err(e) => eprintln!("Unable to fetch photo {}: {}", url, e),
+                }
+            }
+
+            let pdf = cards::render_pdf(&cards, &photos, paper)?;
+            std::fs::write(&path, pdf)
+                .with_context(|| format!("Unable to write {}", path.display()))?;
+        }
+        Commands::Ministering {
+            command: MinisteringCommands::Snapshot { org, path },
+        } => {
+            let ministering = client
+                .ministering(org)
+                .context("Unable to fetch ministering")?;
+            let snapshot = Snapshot::new(org, client.unit_number(), now().date(), ministering);
+            snapshot
+                .save(&path)
+                .with_context(|| format!("Unable to save snapshot to {}", path.display()))?;
+        }
+        Commands::Ministering {
+            command:
+                MinisteringCommands::History {
+                    before,
+                    after,
+                    output,
+                },
+        } => {
+            let before = Snapshot::load(&before)
+                .with_context(|| format!("Unable to load snapshot {}", before.display()))?;
+            let after = Snapshot::load(&after)
+                .with_context(|| format!("Unable to load snapshot {}", after.display()))?;
+            let diff = history::diff_snapshots(&before, &after)?;
+
+            match output {
+                OutputType::Plaintext => print_ministering_history(&diff),
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &diff)?,
+            }
         }
         Commands::Report => {
             let member_list = client.member_list()?;
             print_age_buckets(&member_list);
             print_gender_buckets(&member_list);
 
-            let profiles: Result<Vec<_>> = member_list
-                .iter()
-                .map(|m| {
-                    client
-                        .member_profile(m.legacy_cmis_id)
-                        .context("Unable to fetch member profile")
-                })
-                .collect();
-
-            let profiles = profiles?;
-            let now = OffsetDateTime::now_utc();
+            let ids: Vec<_> = member_list.iter().map(|m| m.legacy_cmis_id).collect();
+            let profiles = client
+                .member_profiles(&ids)
+                .context("Unable to fetch member profiles")?;
+            let now = now();
             let durations: Vec<_> = profiles
                 .iter()
                 .filter_map(|profile| {
@@ -159,6 +794,515 @@ async fn main() -> Result<()> {
 
             print_time_in_ward_buckets(&durations);
         }
+        Commands::Ordinances => {
+            let member_list = client
+                .member_list()
+                .context("Unable to fetch member list")?;
+            // Nobody younger is counted, so skip fetching their profiles.
+            let ids: Vec<_> = member_list
+                .iter()
+                .filter(|m| m.age >= 12)
+                .map(|m| m.legacy_cmis_id)
+                .collect();
+            let profiles = client
+                .member_profiles(&ids)
+                .context("Unable to fetch member profiles")?;
+
+            print_ordinance_summary(&ordinances::summarize(&member_list, &profiles));
+        }
+        Commands::Attendance {
+            command:
+                AttendanceCommands::Show {
+                    org,
+                    from,
+                    to,
+                    output,
+                },
+        } => {
+            let to = to.unwrap_or_else(|| now().date());
+            let class_attendance = client
+                .class_attendance(org, from, to)
+                .context("Unable to fetch attendance")?;
+
+            match output {
+                TabularOutputType::Plaintext => print_attendance(&class_attendance),
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &class_attendance)?
+                }
+                TabularOutputType::Csv => write_csv(attendance::rows(&class_attendance))?,
+            }
+        }
+        Commands::Attendance {
+            command:
+                AttendanceCommands::Import {
+                    org,
+                    date,
+                    dry_run,
+                    path,
+                },
+        } => {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("Unable to read {}", path.display()))?;
+            let is_csv = path
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+
+            let (class_attendance, marks) = if is_csv {
+                let (start, end) = attendance::csv_date_range(text.as_bytes())?;
+                let class_attendance = client
+                    .class_attendance(org, start, end)
+                    .context("Unable to fetch attendance")?;
+                let marks = attendance::read_csv(text.as_bytes(), &class_attendance, start, end)?;
+                (class_attendance, marks)
+            } else {
+                let date = date.context("--date is required for roll-call files")?;
+                let class_attendance = client
+                    .class_attendance(org, date, date)
+                    .context("Unable to fetch attendance")?;
+                let marks = attendance::read_roll_call(&text, date, &class_attendance)?;
+                (class_attendance, marks)
+            };
+
+            // Changes are recorded one at a time and printed as they go, so if one fails it's
+            // clear which ones made it into LCR.
+            let changes = attendance::changes(&class_attendance, &marks);
+            for (i, change) in changes.iter().enumerate() {
+                if !dry_run {
+                    client
+                        .record_attendance(org, &[change.to_update()])
+                        .with_context(|| {
+                            format!(
+                                "Unable to record attendance after recording {} of {} changes",
+                                i,
+                                changes.len()
+                            )
+                        })?;
+                }
+                println!(
+                    "{} {}: {}",
+                    change.date,
+                    change.name,
+                    if change.attended { "present" } else { "absent" }
+                );
+            }
+
+            if !dry_run {
+                println!("Recorded {} changes", changes.len());
+            }
+        }
+        Commands::Sacrament {
+            command:
+                SacramentCommands::Trend {
+                    weeks,
+                    window,
+                    output,
+                },
+        } => {
+            // Go back an extra year so the oldest week printed has one to compare against.
+            let this_year = now().year();
+            let first_year = this_year - 1 - (weeks / 52) as i32;
+            let mut counts = Vec::new();
+            for year in first_year..=this_year {
+                counts.extend(
+                    client
+                        .sacrament_attendance(year)
+                        .context("Unable to fetch sacrament meeting attendance")?,
+                );
+            }
+
+            let trend = sacrament::trend(&counts, window);
+            let recent = &trend[trend.len().saturating_sub(weeks)..];
+            match output {
+                OutputType::Plaintext => print_sacrament_trend(recent),
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), recent)?,
+            }
+        }
+        Commands::Sacrament {
+            command: SacramentCommands::Record { date, count },
+        } => {
+            let date = match date {
+                Some(date) if date.weekday() != Weekday::Sunday => {
+                    bail!("{} is a {}, not a Sunday", date, date.weekday())
+                }
+                Some(date) => date,
+                None => sacrament::most_recent_sunday(now().date()),
+            };
+            client
+                .record_sacrament_attendance(date, count)
+                .context("Unable to record sacrament meeting attendance")?;
+        }
+        Commands::Stake {
+            units,
+            command: StakeCommands::Members { output },
+        } => {
+            let labels = stake_units(&mut client, &units)?;
+            let numbers: Vec<_> = labels.iter().map(|l| l.unit_number.clone()).collect();
+            let lists = client
+                .per_unit(&numbers, |c| c.member_list())
+                .context("Unable to fetch member lists")?;
+            let members = StakeResults::new(labels.into_iter().zip(lists).collect());
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for unit in &members.units {
+                        println!("{} ({})", unit.unit.name, unit.subtotal);
+                        for member in &unit.items {
+                            println!("  {}", member.name_list_preferred_local);
+                        }
+                    }
+                    println!("Total: {}", members.total);
+                }
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &members)?
+                }
+                TabularOutputType::Csv => write_csv(stake::member_rows(&members))?,
+            }
+        }
+        Commands::Stake {
+            units,
+            command: StakeCommands::Moves { months, output },
+        } => {
+            let labels = stake_units(&mut client, &units)?;
+            let (moved_in, moved_out) = fetch_stake_moves(&mut client, labels, months)?;
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for (moved_in, moved_out) in moved_in.units.iter().zip(&moved_out.units) {
+                        println!(
+                            "{} ({} in, {} out)",
+                            moved_in.unit.name, moved_in.subtotal, moved_out.subtotal
+                        );
+                        for person in &moved_in.items {
+                            println!("  in:  {} ({})", person.name, person.move_date);
+                        }
+                        for person in &moved_out.items {
+                            println!("  out: {} ({})", person.name, person.move_date_display);
+                        }
+                    }
+                    println!("Total: {} in, {} out", moved_in.total, moved_out.total);
+                }
+                TabularOutputType::Json => serde_json::to_writer_pretty(
+                    std::io::stdout(),
+                    &serde_json::json!({ "movedIn": moved_in, "movedOut": moved_out }),
+                )?,
+                TabularOutputType::Csv => write_csv(stake::move_rows(&moved_in, &moved_out))?,
+            }
+        }
+        Commands::Stake {
+            units,
+            command: StakeCommands::Report { months, output },
+        } => {
+            let labels = stake_units(&mut client, &units)?;
+            let numbers: Vec<_> = labels.iter().map(|l| l.unit_number.clone()).collect();
+            let lists = client
+                .per_unit(&numbers, |c| c.member_list())
+                .context("Unable to fetch member lists")?;
+            let members = StakeResults::new(labels.clone().into_iter().zip(lists).collect());
+            let (moved_in, moved_out) = fetch_stake_moves(&mut client, labels, months)?;
+            let summary = stake::summarize(&members, &moved_in, &moved_out);
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    print_stake_summary(&summary);
+                    let all_members: Vec<_> = members.iter().map(|(_, m)| m.clone()).collect();
+                    print_age_buckets(&all_members);
+                    print_gender_buckets(&all_members);
+                }
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &summary)?
+                }
+                TabularOutputType::Csv => write_csv(&summary)?,
+            }
+        }
+        Commands::Birthdays {
+            output,
+            months,
+            omit_age,
+            ics,
+        } => {
+            let now = now();
+            let window = client
+                .birthdays(now.month(), months)
+                .context("Unable to fetch birthdays")?;
+            let upcoming = birthdays::upcoming(&window, now.date(), months, !omit_age);
+
+            match output {
+                OutputType::Plaintext => {
+                    for birthday in &upcoming {
+                        let age = birthday
+                            .age
+                            .map(|a| format!(" (turning {})", a))
+                            .unwrap_or_default();
+                        println!(
+                            "{} {:>2}  {}{}",
+                            birthday.date.month(),
+                            birthday.date.day(),
+                            birthday.name,
+                            age
+                        );
+                    }
+                }
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &upcoming)?,
+            }
+
+            if let Some(path) = ics {
+                // The calendar repeats yearly, so it needs everyone, not just the upcoming window.
+                let all_birthdays = client
+                    .birthdays(Month::January, 12)
+                    .context("Unable to fetch birthdays")?;
+                let calendar = birthdays::calendar(&all_birthdays, !omit_age);
+                let utc = now.to_offset(UtcOffset::UTC);
+                let stamp = PrimitiveDateTime::new(utc.date(), utc.time());
+                std::fs::write(&path, calendar.to_ics(stamp))
+                    .with_context(|| format!("Unable to write {}", path.display()))?;
+            }
+        }
+        Commands::Recommends { output, months } => {
+            let statuses = client
+                .recommend_status()
+                .context("Unable to fetch temple recommend status")?;
+            let expiring = recommends::expiring(&statuses, now().date(), months);
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for (month, recommends) in recommends::group_by_month(&expiring) {
+                        println!("{}", month);
+                        for recommend in recommends {
+                            let recommend_type = recommend
+                                .recommend_type
+                                .map(|t| format!(" ({})", t))
+                                .unwrap_or_default();
+                            let expired = if recommend.expired { ", expired" } else { "" };
+                            println!(
+                                "  {}{}: {}{}",
+                                recommend.name, recommend_type, recommend.expiration_date, expired
+                            );
+                        }
+                    }
+                }
+                TabularOutputType::Json => serde_json::to_writer_pretty(
+                    std::io::stdout(),
+                    &recommends::group_by_month(&expiring),
+                )?,
+                TabularOutputType::Csv => write_csv(&expiring)?,
+            }
+        }
+        Commands::Actions { output } => {
+            let to_do = client
+                .action_interview_list()
+                .context("Unable to fetch the action and interview list")?
+                .to_do();
+
+            match output {
+                OutputType::Plaintext => {
+                    for (category, items) in &to_do.iter().chunk_by(|i| i.category) {
+                        println!("{}", category);
+                        for item in items {
+                            match &item.detail {
+                                Some(detail) => println!("  {} ({})", item.name, detail),
+                                None => println!("  {}", item.name),
+                            }
+                        }
+                    }
+                }
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &to_do)?,
+            }
+        }
+        Commands::Interviews {
+            from,
+            days,
+            slots,
+            minutes,
+            ics,
+            output,
+        } => {
+            let now = now();
+            let from = from.unwrap_or(now.date());
+            let member_list = client
+                .member_list()
+                .context("Unable to fetch member list")?;
+            let slots = match slots {
+                Some(path) => {
+                    let file = std::fs::File::open(&path)
+                        .with_context(|| format!("Unable to read {}", path.display()))?;
+                    interviews::read_slots(file)?
+                }
+                None => Vec::new(),
+            };
+
+            let due =
+                interviews::due_interviews(&member_list, from, from + time::Duration::days(days));
+            let appointments = interviews::schedule(&due, &slots);
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for appointment in &appointments {
+                        println!(
+                            "{}  {:<10} {} (age {}): {}",
+                            appointment.due,
+                            appointment.interviewer.to_string(),
+                            appointment.name,
+                            appointment.age,
+                            appointment.start.as_deref().unwrap_or("unscheduled")
+                        );
+                    }
+                }
+                TabularOutputType::Json => {
+                    serde_json::to_writer_pretty(std::io::stdout(), &appointments)?
+                }
+                TabularOutputType::Csv => write_csv(&appointments)?,
+            }
+
+            if let Some(path) = ics {
+                let calendar =
+                    interviews::calendar(&appointments, time::Duration::minutes(minutes));
+                let utc = now.to_offset(UtcOffset::UTC);
+                let stamp = PrimitiveDateTime::new(utc.date(), utc.time());
+                std::fs::write(&path, calendar.to_ics(stamp))
+                    .with_context(|| format!("Unable to write {}", path.display()))?;
+            }
+        }
+        Commands::Milestones { output, months } => {
+            let member_list = client
+                .member_list()
+                .context("Unable to fetch member list")?;
+            let milestones = advancement::upcoming_milestones(&member_list, now().date(), months);
+            let by_month = advancement::group_by_month(&milestones);
+
+            match output {
+                OutputType::Plaintext => {
+                    for (month, milestones) in by_month {
+                        println!("{}", month);
+                        for milestone in milestones {
+                            println!(
+                                "  {} {}: {}",
+                                milestone.date, milestone.name, milestone.description
+                            );
+                        }
+                    }
+                }
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &by_month)?,
+            }
+        }
+        Commands::Units {
+            output,
+            details: None,
+        } => {
+            let units = client.units().context("Unable to fetch units")?;
+
+            match output {
+                OutputType::Plaintext => {
+                    for unit in &units.units {
+                        let home = if unit.unit_number == units.home_unit_number {
+                            " (home)"
+                        } else {
+                            ""
+                        };
+                        println!(
+                            "{:>8}  {} [{}]{}",
+                            unit.unit_number,
+                            unit.name,
+                            unit.unit_type.as_deref().unwrap_or("-"),
+                            home
+                        );
+                        if let Some(parent) = &unit.parent_unit_name {
+                            println!("{:>8}  in {}", "", parent);
+                        }
+                    }
+                }
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &units)?,
+            }
+        }
+        Commands::Units {
+            output,
+            details: Some(unit_number),
+        } => {
+            let details = client
+                .unit_details(&unit_number)
+                .context("Unable to fetch unit details")?;
+
+            match output {
+                OutputType::Plaintext => println!("{:#?}", details),
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &details)?,
+            }
+        }
+        Commands::Callings { output, vacant } => {
+            let orgs = client.callings().context("Unable to fetch callings")?;
+
+            match output {
+                TabularOutputType::Plaintext => {
+                    for org in &orgs {
+                        print_organization(org, 0, vacant);
+                    }
+                }
+                TabularOutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &orgs)?,
+                TabularOutputType::Csv => {
+                    let rows: Vec<_> = callings::calling_rows(&orgs)
+                        .into_iter()
+                        .filter(|r| !vacant || r.vacant)
+                        .collect();
+                    write_csv(&rows)?;
+                }
+            }
+        }
+        Commands::CallingReport { output } => {
+            let orgs = client.callings().context("Unable to fetch callings")?;
+            let member_list = client
+                .member_list()
+                .context("Unable to fetch member list")?;
+            let report = callings::calling_report(&orgs, &member_list, now().date());
+
+            match output {
+                OutputType::Plaintext => print_calling_report(&report),
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &report)?,
+            }
+        }
+        Commands::Find {
+            query,
+            output,
+            profile,
+        } => {
+            let households = client.households().context("Unable to fetch households")?;
+            let members: Vec<_> = households
+                .iter()
+                .flat_map(|h| h.members().cloned())
+                .collect();
+            let matches = search::search(&members, &query);
+
+            let profiles: Vec<Option<MemberProfile>> = if profile {
+                let ids: Vec<_> = matches.iter().map(|m| m.member.legacy_cmis_id).collect();
+                client
+                    .member_profiles(&ids)
+                    .context("Unable to fetch member profiles")?
+                    .into_iter()
+                    .map(Some)
+                    .collect()
+            } else {
+                matches.iter().map(|_| None).collect()
+            };
+
+            let found: Vec<_> = matches
+                .iter()
+                .zip(profiles)
+                .map(|(m, profile)| FoundMember {
+                    member: m.member,
+                    household: households.iter().find(|h| {
+                        h.members()
+                            .any(|p| p.legacy_cmis_id == m.member.legacy_cmis_id)
+                    }),
+                    profile,
+                })
+                .collect();
+
+            match output {
+                OutputType::Plaintext => {
+                    for found in &found {
+                        print_found_member(found);
+                    }
+                }
+                OutputType::Json => serde_json::to_writer_pretty(std::io::stdout(), &found)?,
+            }
+        }
         Commands::VisualMembers => {
             visual_directory::create_visual_directory(&mut client).await?;
         }
@@ -167,12 +1311,226 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn write_csv<T: Serialize>(rows: impl IntoIterator<Item = T>) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    for row in rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+fn print_organization(org: &Organization, depth: usize, only_vacant: bool) {
+    let indent = "  ".repeat(depth);
+    println!("{}{}", indent, org.name);
+    for calling in org
+        .callings
+        .iter()
+        .filter(|c| !only_vacant || c.is_vacant())
+    {
+        let holder = calling.member_name.as_deref().unwrap_or("(vacant)");
+        let sustained = calling
+            .sustained_date()
+            .map(|d| format!(", sustained {}", d))
+            .unwrap_or_default();
+        let set_apart = calling
+            .set_apart_date()
+            .map(|d| format!(", set apart {}", d))
+            .unwrap_or_default();
+        println!(
+            "{}  {}: {}{}{}",
+            indent, calling.position, holder, sustained, set_apart
+        );
+    }
+    for child in &org.children {
+        print_organization(child, depth + 1, only_vacant);
+    }
+}
+
+fn print_calling_report(report: &CallingReport) {
+    println!("Vacant positions:");
+    for row in &report.vacancies {
+        println!(
+            "  {} / {}: {}",
+            row.organization, row.sub_organization, row.position
+        );
+    }
+
+    println!("\nMultiple callings:");
+    for person in &report.multiple_callings {
+        println!("  {}: {}", person.name, person.positions.join(", "));
+    }
+
+    println!("\nAdults without a calling:");
+    for person in &report.adults_without_calling {
+        println!("  {}", person.name);
+    }
+
+    let months: Vec<_> = report.tenures.iter().map(|t| t.months).collect();
+    print_month_buckets("Tenure in calling", &months);
+}
+
+fn print_ministering(ministering: &Ministering) {
+    for quorum in &ministering.quorums {
+        for (district, companionship) in quorum.companionships_by_district() {
+            if let Some(district) = district {
+                print!("[{}] ", district.district_name);
+            }
+            println!(
+                "{}",
+                companionship
+                    .ministers
+                    .iter()
+                    .map(|m| format!("{} ({})", m.name, m.legacy_cmis_id))
+                    .join(" & ")
+            );
+            for assignment in companionship.assignments() {
+                println!("\t{} ({})", assignment.name, assignment.legacy_cmis_id);
+            }
+        }
+    }
+}
+
+fn print_ministering_diff(diff: &PeopleDiff) {
+    let people = match diff.sex {
+        Some(Sex::Female) => "Women",
+        Some(Sex::Male) => "Men",
+        None => "People",
+    };
+
+    println!("{} only in {} ministering:", people, diff.from);
+    for person in &diff.only_in_from {
+        println!("\t{} ({})", person.name, person.legacy_cmis_id);
+    }
+
+    println!("\n{} only in {} ministering:", people, diff.to);
+    for person in &diff.only_in_to {
+        println!("\t{} ({})", person.name, person.legacy_cmis_id);
+    }
+}
+
+fn describe_companionship(companionship: &CompanionshipSummary) -> String {
+    companionship
+        .ministers
+        .iter()
+        .map(|m| format!("{} ({})", m.name, m.legacy_cmis_id))
+        .join(" & ")
+}
+
+fn print_ministering_history(diff: &HistoryDiff) {
+    println!(
+        "{} ministering changes from {} to {}:",
+        diff.organization, diff.from, diff.to
+    );
+    if diff.is_empty() {
+        println!("\tNo changes");
+        return;
+    }
+
+    println!("\nNew companionships:");
+    for companionship in &diff.new_companionships {
+        println!("\t{}", describe_companionship(companionship));
+    }
+
+    println!("\nDissolved companionships:");
+    for companionship in &diff.dissolved_companionships {
+        println!("\t{}", describe_companionship(companionship));
+    }
+
+    println!("\nChanged companionships:");
+    for change in &diff.changed_companionships {
+        println!("\t{}", describe_companionship(&change.after));
+        for minister in &change.added_ministers {
+            println!(
+                "\t\t+ minister {} ({})",
+                minister.name, minister.legacy_cmis_id
+            );
+        }
+        for minister in &change.removed_ministers {
+            println!(
+                "\t\t- minister {} ({})",
+                minister.name, minister.legacy_cmis_id
+            );
+        }
+        for assignment in &change.added_assignments {
+            println!(
+                "\t\t+ assigned {} ({})",
+                assignment.name, assignment.legacy_cmis_id
+            );
+        }
+        for assignment in &change.removed_assignments {
+            println!(
+                "\t\t- assigned {} ({})",
+                assignment.name, assignment.legacy_cmis_id
+            );
+        }
+    }
+
+    println!("\nFamilies with different ministers:");
+    for family in &diff.family_changes {
+        println!(
+            "\t{} ({})",
+            family.family.name, family.family.legacy_cmis_id
+        );
+        for minister in &family.gained_ministers {
+            println!("\t\t+ {} ({})", minister.name, minister.legacy_cmis_id);
+        }
+        for minister in &family.lost_ministers {
+            println!("\t\t- {} ({})", minister.name, minister.legacy_cmis_id);
+        }
+    }
+
+    println!("\nMoved ministers:");
+    for moved in &diff.moved_ministers {
+        println!(
+            "\t{} ({}): {} -> {}",
+            moved.minister.name,
+            moved.minister.legacy_cmis_id,
+            describe_companionship(&moved.from),
+            describe_companionship(&moved.to)
+        );
+    }
+}
+
+fn print_ministering_gaps(report: &GapReport) {
+    println!("{} households without ministers:", report.organization);
+    for household in &report.unassigned_households {
+        println!(
+            "\t{} ({})",
+            household.head.name, household.head.legacy_cmis_id
+        );
+    }
+
+    println!(
+        "\n{} eligible ministers without a companionship:",
+        report.organization
+    );
+    for minister in &report.unpaired_ministers {
+        println!("\t{} ({})", minister.name, minister.legacy_cmis_id);
+    }
+
+    println!(
+        "\n{} companionships without assignments:",
+        report.organization
+    );
+    for companionship in &report.idle_companionships {
+        println!(
+            "\t{}",
+            companionship
+                .ministers
+                .iter()
+                .map(|m| format!("{} ({})", m.name, m.legacy_cmis_id))
+                .join(" & ")
+        );
+    }
+}
+
 fn print_male_emails(members: &[MemberListPerson]) {
     for email in members.iter().filter_map(|m| {
         if m.sex != "M" {
             None
         } else {
-            m.email.as_ref().filter(|email| !email.contains("DNC"))
+            m.contact.email.as_ref()
         }
     }) {
         println!("{}", email);
@@ -212,7 +1570,7 @@ fn print_gender_buckets(members: &[MemberListPerson]) {
 fn print_age_buckets(members: &[MemberListPerson]) {
     let mut map = HashMap::new();
     for member in members {
-        let entry = map.entry(member.age).or_insert(0u8);
+        let entry = map.entry(member.age).or_insert(0u32);
         *entry += 1;
     }
 
@@ -231,7 +1589,225 @@ fn print_age_buckets(members: &[MemberListPerson]) {
     }
 }
 
+fn print_attendance(class_attendance: &ClassAttendance) {
+    let dates: Vec<_> = class_attendance
+        .attendees
+        .iter()
+        .flat_map(|a| a.entries.iter().filter_map(|e| e.date()))
+        .sorted()
+        .dedup()
+        .collect();
+    let name_width = class_attendance
+        .attendees
+        .iter()
+        .map(|a| a.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let header: String = dates
+        .iter()
+        .map(|d| format!(" {:02}/{:02}", u8::from(d.month()), d.day()))
+        .collect();
+    println!("{:name_width$}{}", "", header);
+    for attendee in &class_attendance.attendees {
+        let marks: String = dates
+            .iter()
+            .map(|d| match attendee.attended(*d) {
+                Some(true) => "   x  ",
+                _ => "   .  ",
+            })
+            .collect();
+        println!("{:name_width$}{}", attendee.name, marks);
+    }
+}
+
+fn print_sacrament_trend(weeks: &[TrendWeek]) {
+    // Scale the bars so the largest count fits on one line.
+    const BAR_WIDTH: u32 = 50;
+    let max = weeks.iter().map(|w| w.count).max().unwrap_or(0).max(1);
+
+    println!(
+        "{:^12}{:^7}{:^9}{:^11}{:^8}",
+        "Week", "Count", "Average", "Last year", "Change"
+    );
+    for week in weeks {
+        let last_year = week
+            .last_year
+            .map(|l| l.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let change = week
+            .change_from_last_year
+            .map(|c| format!("{:+}", c))
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:^12}{:^7}{:^9.1}{:^11}{:^8} {}",
+            week.date,
+            week.count,
+            week.rolling_average,
+            last_year,
+            change,
+            "#".repeat((week.count * BAR_WIDTH / max) as usize)
+        );
+    }
+}
+
+/// The units named on the command line, or every ward and branch the user can access.
+fn stake_units(client: &mut Client, unit_numbers: &[String]) -> Result<Vec<UnitLabel>> {
+    let units = client.units().context("Unable to fetch units")?.units;
+    let label = |unit: &lcr::data::Unit| UnitLabel {
+        unit_number: unit.unit_number.to_string(),
+        name: unit.name.clone(),
+    };
+
+    if unit_numbers.is_empty() {
+        return Ok(units
+            .iter()
+            .filter(|u| u.is_ward_or_branch())
+            .map(label)
+            .collect());
+    }
+
+    let unknown: Vec<_> = unit_numbers
+        .iter()
+        .filter(|number| !units.iter().any(|u| u.unit_number.to_string() == **number))
+        .collect();
+    if !unknown.is_empty() {
+        bail!("Not a unit you can access: {}", unknown.iter().join(", "));
+    }
+
+    Ok(unit_numbers
+        .iter()
+        .filter_map(|number| {
+            units
+                .iter()
+                .find(|u| u.unit_number.to_string() == *number)
+                .map(label)
+        })
+        .collect())
+}
+
+fn fetch_stake_moves(
+    client: &mut Client,
+    labels: Vec<UnitLabel>,
+    months: u8,
+) -> Result<(StakeResults<MovedInPerson>, StakeResults<MovedOutPerson>)> {
+    let numbers: Vec<_> = labels.iter().map(|l| l.unit_number.clone()).collect();
+    let moves = client
+        .per_unit(&numbers, |c| {
+            Ok((c.moved_in(months)?, c.moved_out(months)?))
+        })
+        .context("Unable to fetch moves")?;
+    let (moved_in, moved_out): (Vec<_>, Vec<_>) = moves.into_iter().unzip();
+
+    Ok((
+        StakeResults::new(labels.clone().into_iter().zip(moved_in).collect()),
+        StakeResults::new(labels.into_iter().zip(moved_out).collect()),
+    ))
+}
+
+fn print_stake_summary(summary: &[UnitSummary]) {
+    let name_width = summary.iter().map(|s| s.unit.len()).max().unwrap_or(0);
+    println!(
+        "{:name_width$}{:>9}{:>8}{:>7}{:>10}{:>7}{:>9}{:>10}{:>11}",
+        "", "Members", "Adults", "Youth", "Children", "Males", "Females", "Moved in", "Moved out"
+    );
+    for row in summary {
+        println!(
+            "{:name_width$}{:>9}{:>8}{:>7}{:>10}{:>7}{:>9}{:>10}{:>11}",
+            row.unit,
+            row.members,
+            row.adults,
+            row.youth,
+            row.children,
+            row.males,
+            row.females,
+            row.moved_in,
+            row.moved_out
+        );
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FoundMember<'a> {
+    member: &'a MemberListPerson,
+    household: Option<&'a Household>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<MemberProfile>,
+}
+
+fn print_found_member(found: &FoundMember) {
+    let member = found.member;
+    println!(
+        "{} ({}, age {})",
+        member.name_list_preferred_local, member.legacy_cmis_id, member.age
+    );
+
+    if let Some(household) = found.household {
+        println!("  Household: {}", household.head.name_list_preferred_local);
+        if let Some(address) = &household.address {
+            for line in &address.address_lines {
+                println!("    {}", line);
+            }
+        }
+    }
+
+    let contact = &member.contact;
+    if contact.do_not_contact {
+        println!("  Do not contact");
+    }
+    if let Some(phone_number) = &contact.phone_number {
+        println!("  Phone: {}", phone_number);
+    }
+    if let Some(email) = &contact.email {
+        println!("  Email: {}", email);
+    }
+
+    if let Some(profile) = &found.profile {
+        println!("{:#?}", profile);
+    }
+    println!();
+}
+
+fn print_ordinance_summary(summary: &OrdinanceSummary) {
+    println!("Adults: {}", summary.adults);
+
+    println!("\nOrdinances received by adults:");
+    for (ordinance_type, count) in &summary.ordinance_counts {
+        println!(
+            "{:<24}{:^7} {}",
+            ordinance_type.to_string(),
+            count,
+            "#".repeat(*count)
+        );
+    }
+
+    println!("\nUnendowed adults by age:\n{:^7}{:^7}", "Age", "Count");
+    for (age, count) in &summary.unendowed_by_age_group {
+        println!(
+            "{:^7}{:^7} {}",
+            format!("{}+", age),
+            count,
+            "#".repeat(*count)
+        );
+    }
+
+    println!("\nNot ordained to the priesthood:");
+    for person in &summary.unordained {
+        println!("  {}", person.name);
+    }
+
+    println!("\nWithout the Melchizedek Priesthood:");
+    for person in &summary.without_melchizedek_priesthood {
+        println!("  {}", person.name);
+    }
+}
+
 fn print_time_in_ward_buckets(month_vec: &[i64]) {
+    print_month_buckets("Time in ward", month_vec);
+}
+
+fn print_month_buckets(title: &str, month_vec: &[i64]) {
     let mut map = HashMap::new();
     for num_months in month_vec {
         let entry = map.entry(num_months).or_insert(0u8);
@@ -245,8 +1821,8 @@ fn print_time_in_ward_buckets(month_vec: &[i64]) {
     let total_count = month_vec.len();
 
     println!(
-        "\nTime in ward buckets:\n{:^7}{:^7}{:^7}{:^7}",
-        "Months", "Count", "Running", "Percent"
+        "\n{} buckets:\n{:^7}{:^7}{:^7}{:^7}",
+        title, "Months", "Count", "Running", "Percent"
     );
     for key in keys {
         let num = map[key];
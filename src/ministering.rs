@@ -0,0 +1,71 @@
+//! Tools built on top of the ministering data returned by `Client::ministering`.
+
+use serde::Serialize;
+
+pub use crate::data::PersonRef;
+use crate::data::{Ministering, MinisteringOrganization};
+
+pub mod cards;
+pub mod diff;
+pub mod gaps;
+pub mod graph;
+pub mod history;
+pub mod proposal;
+
+/// One row per assignment, or per companionship if it has none, for flat exports like CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentRow<'a> {
+    pub organization: MinisteringOrganization,
+    pub district: Option<&'a str>,
+    pub supervisor: Option<&'a str>,
+    pub companionship_id: Option<&'a str>,
+    pub ministers: String,
+    pub minister_ids: String,
+    pub assignment: Option<&'a str>,
+    pub assignment_id: Option<u64>,
+}
+
+pub fn assignment_rows<'a>(
+    org: MinisteringOrganization,
+    ministering: &'a Ministering,
+) -> Vec<AssignmentRow<'a>> {
+    let mut rows = Vec::new();
+    for quorum in &ministering.quorums {
+        for (district, companionship) in quorum.companionships_by_district() {
+            let ministers = companionship
+                .ministers
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            let minister_ids = companionship
+                .minister_ids()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+
+            let row = |assignment: Option<(&'a str, u64)>| AssignmentRow {
+                organization: org,
+                district: district.map(|d| d.district_name.as_str()),
+                supervisor: district.and_then(|d| d.supervisor_name.as_deref()),
+                companionship_id: companionship.id.as_deref(),
+                ministers: ministers.clone(),
+                minister_ids: minister_ids.clone(),
+                assignment: assignment.map(|(name, _)| name),
+                assignment_id: assignment.map(|(_, id)| id),
+            };
+
+            if companionship.assignments().is_empty() {
+                rows.push(row(None));
+            }
+            for assignment in companionship.assignments() {
+                rows.push(row(Some((
+                    assignment.name.as_str(),
+                    assignment.legacy_cmis_id,
+                ))));
+            }
+        }
+    }
+    rows
+}
@@ -0,0 +1,369 @@
+//! Printable cards, one per companionship, listing the families assigned to it.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use printpdf::{
+    BuiltinFont, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference,
+    Point, image_crate,
+};
+use serde::Serialize;
+
+use crate::data::{Household, Ministering, VisualPerson};
+use crate::error::Error;
+use crate::names::normalize;
+
+// How many cards are laid out across and down each page.
+const CARDS_ACROSS: usize = 2;
+const CARDS_DOWN: usize = 2;
+
+const PAGE_MARGIN: f32 = 10.0;
+const CARD_GUTTER: f32 = 6.0;
+const CARD_PADDING: f32 = 4.0;
+
+// Height of one family's entry on a card, which is also the photo size.
+const FAMILY_HEIGHT: f32 = 20.0;
+const TITLE_HEIGHT: f32 = 10.0;
+
+// Photos are shrunk to this many pixels on a side before embedding, since printpdf stores them
+// uncompressed.
+const PHOTO_PIXELS: u32 = 240;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    Letter,
+    A4,
+}
+
+impl PaperSize {
+    /// Width and height in millimeters, portrait.
+    fn dimensions(self) -> (f32, f32) {
+        match self {
+            Self::Letter => (215.9, 279.4),
+            Self::A4 => (210.0, 297.0),
+        }
+    }
+}
+
+impl FromStr for PaperSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "letter" => Ok(Self::Letter),
+            "a4" => Ok(Self::A4),
+            _ => Err(format!("Unknown paper size: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Card {
+    pub ministers: Vec<String>,
+    pub families: Vec<CardFamily>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardFamily {
+    pub name: String,
+    pub address_lines: Vec<String>,
+    pub phone_number: Option<String>,
+    pub photo_url: Option<String>,
+}
+
+/// Finds a household's photo in the visual member list, by the ID of anyone in the household.
+/// Photos without an ID have to match the head's full name exactly, ignoring case and accents.
+fn photo_url(household: &Household, photos: &[VisualPerson]) -> Option<String> {
+    let name = normalize(&format!(
+        "{} {}",
+        household.head.name_given_preferred_local, household.head.name_family_preferred_local
+    ));
+    photos
+        .iter()
+        .find(|p| match p.legacy_cmis_id {
+            Some(id) => household.members().any(|m| m.legacy_cmis_id == id),
+            None => normalize(&p.name) == name,
+        })
+        .map(|p| p.photo_url.clone())
+        .filter(|url| !url.ends_with(".svg"))
+}
+
+pub fn build_cards(
+    ministering: &Ministering,
+    households: &[Household],
+    photos: &[VisualPerson],
+) -> Vec<Card> {
+    let household_of: HashMap<u64, &Household> = households
+        .iter()
+        .flat_map(|h| h.members().map(move |m| (m.legacy_cmis_id, h)))
+        .collect();
+
+    ministering
+        .all_companionships()
+        .map(|companionship| Card {
+            ministers: companionship
+                .ministers
+                .iter()
+                .map(|m| m.name.clone())
+                .collect(),
+            families: companionship
+                .assignments()
+                .iter()
+                .map(
+                    |assignment| match household_of.get(&assignment.legacy_cmis_id) {
+                        Some(household) => CardFamily {
+                            name: assignment.name.clone(),
+                            address_lines: household
+                                .address
+                                .as_ref()
+                                .map(|a| a.address_lines.clone())
+                                .unwrap_or_default(),
+                            phone_number: household
+                                .contact
+                                .phone_number
+                                .as_ref()
+                                .map(|p| p.to_string()),
+                            photo_url: photo_url(household, photos),
+                        },
+                        None => CardFamily {
+                            name: assignment.name.clone(),
+                            address_lines: Vec::new(),
+                            phone_number: None,
+                            photo_url: None,
+                        },
+                    },
+                )
+                .collect(),
+        })
+        .collect()
+}
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+fn pdf_error(e: printpdf::Error) -> Error {
+    Error::Pdf(e.to_string())
+}
+
+fn draw_box(layer: &PdfLayerReference, x: f32, y: f32, width: f32, height: f32) {
+    let corners = [
+        (x, y),
+        (x + width, y),
+        (x + width, y + height),
+        (x, y + height),
+    ];
+    layer.add_line(Line {
+        points: corners
+            .iter()
+            .map(|(x, y)| (Point::new(Mm(*x), Mm(*y)), false))
+            .collect(),
+        is_closed: true,
+    });
+}
+
+fn draw_photo(layer: &PdfLayerReference, bytes: &[u8], x: f32, y: f32) {
+    // A photo that won't decode just gets left off the card.
+    let Ok(image) = image_crate::load_from_memory(bytes) else {
+        return;
+    };
+    let image = image.thumbnail(PHOTO_PIXELS, PHOTO_PIXELS);
+    let size = FAMILY_HEIGHT - 2.0;
+    let longest_side = image.width().max(image.height()) as f32;
+
+    Image::from_dynamic_image(&image).add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(x)),
+            translate_y: Some(Mm(y)),
+            dpi: Some(longest_side / (size / 25.4)),
+            ..Default::default()
+        },
+    );
+}
+
+/// `x` and `y` are the bottom left corner of the card, in millimeters.
+#[allow(clippy::too_many_arguments)]
+fn draw_card(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    card: &Card,
+    photos: &HashMap<String, Vec<u8>>,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+) {
+    draw_box(layer, x, y, width, height);
+
+    let left = x + CARD_PADDING;
+    let mut top = y + height - CARD_PADDING;
+    layer.use_text(
+        card.ministers.join(" & "),
+        12.0,
+        Mm(left),
+        Mm(top - 5.0),
+        &fonts.bold,
+    );
+    top -= TITLE_HEIGHT;
+
+    let fits = ((height - TITLE_HEIGHT - 2.0 * CARD_PADDING) / FAMILY_HEIGHT).floor() as usize;
+    let shown = if card.families.len() > fits {
+        fits.saturating_sub(1)
+    } else {
+        card.families.len()
+    };
+
+    for family in &card.families[..shown] {
+        let bottom = top - FAMILY_HEIGHT;
+        if let Some(bytes) = family.photo_url.as_ref().and_then(|url| photos.get(url)) {
+            draw_photo(layer, bytes, left, bottom + 1.0);
+        }
+
+        let text_left = Mm(left + FAMILY_HEIGHT + 2.0);
+        let mut line_top = top - 4.0;
+        layer.use_text(&family.name, 10.0, text_left, Mm(line_top), &fonts.bold);
+        for line in family
+            .address_lines
+            .iter()
+            .chain(family.phone_number.iter())
+        {
+            line_top -= 3.8;
+            layer.use_text(line, 8.0, text_left, Mm(line_top), &fonts.regular);
+        }
+
+        top = bottom;
+    }
+
+    if shown < card.families.len() {
+        layer.use_text(
+            format!("...and {} more", card.families.len() - shown),
+            9.0,
+            Mm(left),
+            Mm(top - 5.0),
+            &fonts.regular,
+        );
+    }
+}
+
+/// Renders the cards, several per page. `photos` maps photo URLs to downloaded image bytes; cards
+/// for families whose photo is missing or can't be decoded just don't get one.
+///
+/// # Errors
+/// Errors from building the PDF.
+pub fn render_pdf(
+    cards: &[Card],
+    photos: &HashMap<String, Vec<u8>>,
+    paper: PaperSize,
+) -> Result<Vec<u8>, Error> {
+    let (page_width, page_height) = paper.dimensions();
+    let card_width = (page_width - 2.0 * PAGE_MARGIN - (CARDS_ACROSS - 1) as f32 * CARD_GUTTER)
+        / CARDS_ACROSS as f32;
+    let card_height = (page_height - 2.0 * PAGE_MARGIN - (CARDS_DOWN - 1) as f32 * CARD_GUTTER)
+        / CARDS_DOWN as f32;
+
+    let (doc, first_page, first_layer) = PdfDocument::new(
+        "Ministering Cards",
+        Mm(page_width),
+        Mm(page_height),
+        "Cards",
+    );
+    let fonts = Fonts {
+        regular: doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(pdf_error)?,
+        bold: doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(pdf_error)?,
+    };
+
+    for (page_index, page_cards) in cards.chunks(CARDS_ACROSS * CARDS_DOWN).enumerate() {
+        let layer = if page_index == 0 {
+            doc.get_page(first_page).get_layer(first_layer)
+        } else {
+            let (page, layer) = doc.add_page(Mm(page_width), Mm(page_height), "Cards");
+            doc.get_page(page).get_layer(layer)
+        };
+
+        for (i, card) in page_cards.iter().enumerate() {
+            let column = i % CARDS_ACROSS;
+            let row = i / CARDS_ACROSS;
+            let x = PAGE_MARGIN + column as f32 * (card_width + CARD_GUTTER);
+            let y = page_height
+                - PAGE_MARGIN
+                - (row + 1) as f32 * card_height
+                - row as f32 * CARD_GUTTER;
+            draw_card(&layer, &fonts, card, photos, x, y, card_width, card_height);
+        }
+    }
+
+    doc.save_to_bytes().map_err(pdf_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MemberListPerson;
+
+    #[test]
+    fn test_build_and_render_cards() {
+        let ministering: Ministering = serde_json::from_str(
+            r#"{"elders": [{"companionships": [
+                {"ministers": [{"name": "One", "legacyCmisId": 1}, {"name": "Two", "legacyCmisId": 2}],
+                 "assignments": [{"name": "Family Three", "legacyCmisId": 3}, {"name": "Gone", "legacyCmisId": 9}]}
+            ]}]}"#,
+        )
+        .unwrap();
+        let mut three = MemberListPerson::test(3, 40, "F", "c");
+        three.phone_number = Some("801-555-1234".to_string());
+        three.normalize_contact(1);
+        let households = Household::group(
+            vec![
+                MemberListPerson::test(1, 40, "M", "a"),
+                MemberListPerson::test(2, 40, "M", "b"),
+                three,
+            ],
+            1,
+        );
+        let photos = vec![
+            // Only a substring of the head's name, so it isn't theirs.
+            VisualPerson {
+                name: "Given3 Family Jr".to_string(),
+                legacy_cmis_id: None,
+                photo_url: "https://example.com/other.jpg".to_string(),
+            },
+            VisualPerson {
+                name: "Given3 Family".to_string(),
+                legacy_cmis_id: None,
+                photo_url: "https://example.com/3.jpg".to_string(),
+            },
+            VisualPerson {
+                name: "Someone Else".to_string(),
+                legacy_cmis_id: Some(1),
+                photo_url: "https://example.com/1.jpg".to_string(),
+            },
+        ];
+
+        let cards = build_cards(&ministering, &households, &photos);
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].ministers, vec!["One", "Two"]);
+        assert_eq!(
+            cards[0].families[0].phone_number.as_deref(),
+            Some("+18015551234")
+        );
+        assert_eq!(
+            cards[0].families[0].photo_url.as_deref(),
+            Some("https://example.com/3.jpg")
+        );
+        assert_eq!(cards[0].families[1].name, "Gone");
+        assert!(cards[0].families[1].address_lines.is_empty());
+
+        // Undecodable photos are skipped rather than failing the whole document.
+        let bytes = HashMap::from([("https://example.com/3.jpg".to_string(), vec![1, 2, 3])]);
+        let pdf = render_pdf(&cards, &bytes, PaperSize::Letter).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use super::PersonRef;
+use crate::data::{MemberListPerson, Ministering, MinisteringOrganization, Sex};
+
+/// Who shows up in one organization's ministering but not another's.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeopleDiff {
+    pub from: MinisteringOrganization,
+    pub to: MinisteringOrganization,
+    pub sex: Option<Sex>,
+    pub only_in_from: Vec<PersonRef>,
+    pub only_in_to: Vec<PersonRef>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeopleDiffRow<'a> {
+    pub only_in: MinisteringOrganization,
+    pub legacy_cmis_id: u64,
+    pub name: &'a str,
+}
+
+impl PeopleDiff {
+    pub fn rows(&self) -> Vec<PeopleDiffRow<'_>> {
+        let from = self.only_in_from.iter().map(|p| (self.from, p));
+        let to = self.only_in_to.iter().map(|p| (self.to, p));
+        from.chain(to)
+            .map(|(only_in, person)| PeopleDiffRow {
+                only_in,
+                legacy_cmis_id: person.legacy_cmis_id,
+                name: &person.name,
+            })
+            .collect()
+    }
+}
+
+/// Compares everyone (ministers and assignments) in two organizations' ministering data by
+/// legacy CMIS ID, optionally only looking at one sex. People missing from the member list are
+/// kept regardless of `sex`, since there's no way to tell.
+pub fn diff_people(
+    (from, from_ministering): (MinisteringOrganization, &Ministering),
+    (to, to_ministering): (MinisteringOrganization, &Ministering),
+    members: &[MemberListPerson],
+    sex: Option<Sex>,
+) -> PeopleDiff {
+    let members_by_id = MemberListPerson::index_by_id(members);
+    let ids = |ministering: &Ministering| {
+        let mut set = ministering.ids();
+        set.retain(|id| match (sex, members_by_id.get(id)) {
+            (Some(sex), Some(member)) => sex.matches(member),
+            _ => true,
+        });
+        set
+    };
+    let from_ids = ids(from_ministering);
+    let to_ids = ids(to_ministering);
+
+    let mut names = from_ministering.names_by_id();
+    names.extend(to_ministering.names_by_id());
+    let people = |ids: HashSet<&u64>| {
+        let mut people: Vec<_> = ids
+            .into_iter()
+            .map(|id| PersonRef::resolve(*id, &members_by_id, &names))
+            .collect();
+        people.sort_by(|a, b| a.name.cmp(&b.name));
+        people
+    };
+
+    PeopleDiff {
+        from,
+        to,
+        sex,
+        only_in_from: people(from_ids.difference(&to_ids).collect()),
+        only_in_to: people(to_ids.difference(&from_ids).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_people() {
+        let eq: Ministering = serde_json::from_str(
+            r#"{"elders": [{"companionships": [{
+                "ministers": [{"name": "One", "legacyCmisId": 1}],
+                "assignments": [{"name": "Two", "legacyCmisId": 2}, {"name": "Gone", "legacyCmisId": 9}]
+            }]}]}"#,
+        )
+        .unwrap();
+        let rs: Ministering = serde_json::from_str(
+            r#"{"reliefSociety": [{"companionships": [{
+                "ministers": [{"name": "Three", "legacyCmisId": 3}],
+                "assignments": [{"name": "Two", "legacyCmisId": 2}]
+            }]}]}"#,
+        )
+        .unwrap();
+        let members = vec![
+            MemberListPerson::test(1, 40, "M", "a"),
+            MemberListPerson::test(2, 40, "F", "b"),
+            MemberListPerson::test(3, 40, "F", "c"),
+        ];
+
+        let diff = diff_people(
+            (MinisteringOrganization::EldersQuorum, &eq),
+            (MinisteringOrganization::ReliefSociety, &rs),
+            &members,
+            Some(Sex::Female),
+        );
+        assert_eq!(
+            diff.only_in_from,
+            vec![PersonRef {
+                legacy_cmis_id: 9,
+                name: "Gone".to_string()
+            }]
+        );
+        assert_eq!(diff.only_in_to[0].legacy_cmis_id, 3);
+        assert_eq!(diff.rows().len(), 2);
+
+        let diff = diff_people(
+            (MinisteringOrganization::EldersQuorum, &eq),
+            (MinisteringOrganization::ReliefSociety, &rs),
+            &members,
+            None,
+        );
+        assert_eq!(diff.only_in_from.len(), 2);
+    }
+}
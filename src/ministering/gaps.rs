@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use super::PersonRef;
+use crate::data::{Household, Ministering, MinisteringOrganization};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GapReport {
+    pub organization: MinisteringOrganization,
+
+    /// Households where nobody is assigned to a companionship in either organization.
+    pub unassigned_households: Vec<UnassignedHousehold>,
+
+    /// Eligible ministers who aren't in any companionship.
+    pub unpaired_ministers: Vec<PersonRef>,
+
+    /// Companionships that don't have anyone assigned to them.
+    pub idle_companionships: Vec<IdleCompanionship>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnassignedHousehold {
+    pub uuid: String,
+    pub head: PersonRef,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleCompanionship {
+    pub id: Option<String>,
+    pub ministers: Vec<PersonRef>,
+}
+
+/// One line per gap, for flat output like CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GapRow<'a> {
+    pub gap: &'static str,
+    pub legacy_cmis_id: Option<u64>,
+    pub name: String,
+    pub companionship_id: Option<&'a str>,
+}
+
+impl GapReport {
+    pub fn rows(&self) -> Vec<GapRow<'_>> {
+        let households = self.unassigned_households.iter().map(|h| GapRow {
+            gap: "unassigned household",
+            legacy_cmis_id: Some(h.head.legacy_cmis_id),
+            name: h.head.name.clone(),
+            companionship_id: None,
+        });
+        let ministers = self.unpaired_ministers.iter().map(|m| GapRow {
+            gap: "unpaired minister",
+            legacy_cmis_id: Some(m.legacy_cmis_id),
+            name: m.name.clone(),
+            companionship_id: None,
+        });
+        let companionships = self.idle_companionships.iter().map(|c| GapRow {
+            gap: "companionship without assignments",
+            legacy_cmis_id: None,
+            name: c
+                .ministers
+                .iter()
+                .map(|m| m.name.as_str())
+                .collect::<Vec<_>>()
+                .join(" & "),
+            companionship_id: c.id.as_deref(),
+        });
+
+        households.chain(ministers).chain(companionships).collect()
+    }
+}
+
+/// Joins an organization's ministering data with the unit's households to find who is falling
+/// through the cracks. `other` is the other organization's ministering: a household ministered
+/// to by either one isn't a gap.
+pub fn find_gaps(
+    org: MinisteringOrganization,
+    ministering: &Ministering,
+    other: &Ministering,
+    households: &[Household],
+) -> GapReport {
+    let mut assigned = HashSet::new();
+    let mut ministers = HashSet::new();
+    for companionship in ministering.all_companionships() {
+        assigned.extend(companionship.assigned_ids());
+        ministers.extend(companionship.minister_ids());
+    }
+    for companionship in other.all_companionships() {
+        assigned.extend(companionship.assigned_ids());
+    }
+
+    let unassigned_households = households
+        .iter()
+        .filter(|h| !h.members().any(|m| assigned.contains(&m.legacy_cmis_id)))
+        .map(|h| UnassignedHousehold {
+            uuid: h.uuid.clone(),
+            head: PersonRef {
+                legacy_cmis_id: h.head.legacy_cmis_id,
+                name: h.head.name_list_preferred_local.clone(),
+            },
+        })
+        .collect();
+
+    let unpaired_ministers = households
+        .iter()
+        .flat_map(|h| h.members())
+        .filter(|m| org.is_eligible_minister(m) && !ministers.contains(&m.legacy_cmis_id))
+        .map(|m| PersonRef {
+            legacy_cmis_id: m.legacy_cmis_id,
+            name: m.name_list_preferred_local.clone(),
+        })
+        .collect();
+
+    let idle_companionships = ministering
+        .all_companionships()
+        .filter(|c| c.assignments().is_empty())
+        .map(|c| IdleCompanionship {
+            id: c.id.clone(),
+            ministers: c
+                .ministers
+                .iter()
+                .map(|m| PersonRef {
+                    legacy_cmis_id: m.legacy_cmis_id,
+                    name: m.name.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    GapReport {
+        organization: org,
+        unassigned_households,
+        unpaired_ministers,
+        idle_companionships,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MemberListPerson;
+
+    #[test]
+    fn test_find_gaps() {
+        let ministering: Ministering = serde_json::from_str(
+            r#"{"elders": [{"companionships": [
+                {
+                    "id": "c1",
+                    "ministers": [{"name": "One", "legacyCmisId": 1}, {"name": "Two", "legacyCmisId": 2}],
+                    "assignments": [{"name": "Three", "legacyCmisId": 3}]
+                },
+                {
+                    "id": "c2",
+                    "ministers": [{"name": "Four", "legacyCmisId": 4}],
+                    "assignments": []
+                }
+            ]}]}"#,
+        )
+        .unwrap();
+
+        let households = Household::group(
+            vec![
+                MemberListPerson::test(1, 40, "M", "a"),
+                MemberListPerson::test(2, 30, "M", "b"),
+                MemberListPerson::test(3, 50, "M", "c"),
+                MemberListPerson::test(4, 60, "M", "d"),
+                MemberListPerson::test(5, 20, "M", "e"),
+                MemberListPerson::test(6, 20, "F", "f"),
+                MemberListPerson::test(7, 15, "M", "g"),
+            ],
+            1,
+        );
+
+        // Household f is ministered to by the Relief Society, so it isn't an EQ gap either.
+        let relief_society: Ministering = serde_json::from_str(
+            r#"{"reliefSociety": [{"companionships": [
+                {"ministers": [{"name": "Eight", "legacyCmisId": 8}],
+                 "assignments": [{"name": "Six", "legacyCmisId": 6}]}
+            ]}]}"#,
+        )
+        .unwrap();
+
+        let report = find_gaps(
+            MinisteringOrganization::EldersQuorum,
+            &ministering,
+            &relief_society,
+            &households,
+        );
+
+        let unassigned: Vec<_> = report
+            .unassigned_households
+            .iter()
+            .map(|h| h.head.legacy_cmis_id)
+            .collect();
+        assert_eq!(unassigned, vec![1, 2, 4, 5, 7]);
+
+        let unpaired: Vec<_> = report
+            .unpaired_ministers
+            .iter()
+            .map(|m| m.legacy_cmis_id)
+            .collect();
+        assert_eq!(unpaired, vec![3, 5]);
+
+        assert_eq!(report.idle_companionships.len(), 1);
+        assert_eq!(report.idle_companionships[0].id.as_deref(), Some("c2"));
+        assert_eq!(report.rows().len(), 8);
+    }
+}
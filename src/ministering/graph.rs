@@ -0,0 +1,212 @@
+//! Renders ministering as an org chart: organization, then districts, then companionships, each
+//! with its ministers and (dashed) the households assigned to them.
+
+use std::fmt::Write;
+
+use crate::data::{District, Ministering, MinisteringOrganization};
+
+// Nodes are numbered per graph instead of using legacy CMIS IDs, since the same person can show
+// up more than once (e.g. as a minister and as an assignment).
+struct Node {
+    id: String,
+    label: String,
+}
+
+enum Edge {
+    Solid,
+    Dashed,
+}
+
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<(usize, usize, Edge)>,
+}
+
+impl Graph {
+    fn add_node(&mut self, label: String) -> usize {
+        self.nodes.push(Node {
+            id: format!("n{}", self.nodes.len()),
+            label,
+        });
+        self.nodes.len() - 1
+    }
+}
+
+fn district_label(district: Option<&District>) -> Vec<String> {
+    match district {
+        Some(district) => std::iter::once(district.district_name.clone())
+            .chain(
+                district
+                    .supervisor_name
+                    .as_ref()
+                    .map(|s| format!("Supervisor: {}", s)),
+            )
+            .collect(),
+        None => vec!["No district".to_string()],
+    }
+}
+
+fn build(org: MinisteringOrganization, ministering: &Ministering, line_break: &str) -> Graph {
+    let mut graph = Graph {
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+    let root = graph.add_node(org.to_string());
+
+    let mut count = 0;
+    for quorum in &ministering.quorums {
+        let districts = quorum
+            .districts
+            .iter()
+            .map(|d| (Some(d), d.companionships.as_slice()))
+            .chain(
+                (!quorum.companionships.is_empty())
+                    .then_some((None, quorum.companionships.as_slice())),
+            );
+
+        for (district, companionships) in districts {
+            let district_node = graph.add_node(district_label(district).join(line_break));
+            graph.edges.push((root, district_node, Edge::Solid));
+
+            for companionship in companionships {
+                count += 1;
+                let label = match &companionship.id {
+                    Some(id) => format!("Companionship {}", id),
+                    None => format!("Companionship {}", count),
+                };
+                let companionship_node = graph.add_node(label);
+                graph
+                    .edges
+                    .push((district_node, companionship_node, Edge::Solid));
+
+                for minister in &companionship.ministers {
+                    let node = graph.add_node(minister.name.clone());
+                    graph.edges.push((companionship_node, node, Edge::Solid));
+                }
+                for assignment in companionship.assignments() {
+                    let node = graph.add_node(assignment.name.clone());
+                    graph.edges.push((companionship_node, node, Edge::Dashed));
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// Renders the graph in Graphviz DOT format.
+pub fn to_dot(org: MinisteringOrganization, ministering: &Ministering) -> String {
+    let graph = build(org, ministering, "\\n");
+    let mut out = String::new();
+    writeln!(out, "digraph ministering {{").unwrap();
+    writeln!(out, "    rankdir=LR;").unwrap();
+    writeln!(out, "    node [shape=box];").unwrap();
+    for node in &graph.nodes {
+        // Line breaks were already escaped when the label was built.
+        let label = node.label.replace('"', "\\\"");
+        writeln!(out, "    {} [label=\"{}\"];", node.id, label).unwrap();
+    }
+    for (from, to, edge) in &graph.edges {
+        let style = match edge {
+            Edge::Solid => "",
+            Edge::Dashed => " [style=dashed]",
+        };
+        writeln!(
+            out,
+            "    {} -> {}{};",
+            graph.nodes[*from].id, graph.nodes[*to].id, style
+        )
+        .unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// Renders the graph as a Mermaid flowchart.
+pub fn to_mermaid(org: MinisteringOrganization, ministering: &Ministering) -> String {
+    let graph = build(org, ministering, "<br/>");
+    let mut out = String::new();
+    writeln!(out, "flowchart LR").unwrap();
+    for node in &graph.nodes {
+        let label = node.label.replace('"', "#quot;");
+        writeln!(out, "    {}[\"{}\"]", node.id, label).unwrap();
+    }
+    for (from, to, edge) in &graph.edges {
+        let arrow = match edge {
+            Edge::Solid => "-->",
+            Edge::Dashed => "-.->",
+        };
+        writeln!(
+            out,
+            "    {} {} {}",
+            graph.nodes[*from].id, arrow, graph.nodes[*to].id
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ministering() -> Ministering {
+        serde_json::from_str(
+            r#"{"elders": [{
+                "districts": [{
+                    "districtName": "District 1",
+                    "supervisorName": "Smith, \"Jack\"",
+                    "companionships": [{
+                        "id": "c1",
+                        "ministers": [{"name": "Doe, Jim", "legacyCmisId": 1}],
+                        "assignments": [{"name": "Roe Family", "legacyCmisId": 2}]
+                    }]
+                }],
+                "companionships": [{
+                    "ministers": [{"name": "Poe, Sam", "legacyCmisId": 3}],
+                    "assignments": null
+                }]
+            }]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(MinisteringOrganization::EldersQuorum, &ministering());
+        assert_eq!(
+            dot,
+            r#"digraph ministering {
+    rankdir=LR;
+    node [shape=box];
+    n0 [label="Elders Quorum"];
+    n1 [label="District 1\nSupervisor: Smith, \"Jack\""];
+    n2 [label="Companionship c1"];
+    n3 [label="Doe, Jim"];
+    n4 [label="Roe Family"];
+    n5 [label="No district"];
+    n6 [label="Companionship 2"];
+    n7 [label="Poe, Sam"];
+    n0 -> n1;
+    n1 -> n2;
+    n2 -> n3;
+    n2 -> n4 [style=dashed];
+    n0 -> n5;
+    n5 -> n6;
+    n6 -> n7;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = to_mermaid(MinisteringOrganization::EldersQuorum, &ministering());
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(
+            mermaid.contains("    n1[\"District 1<br/>Supervisor: Smith, #quot;Jack#quot;\"]\n")
+        );
+        assert!(mermaid.contains("    n2 -.-> n4\n"));
+        assert!(mermaid.contains("    n5 --> n6\n"));
+    }
+}
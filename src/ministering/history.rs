@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::PersonRef;
+use crate::data::{Companionship, Ministering, MinisteringOrganization};
+use crate::error::Error;
+
+/// Ministering data saved at a point in time, so later data can be compared against it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub organization: MinisteringOrganization,
+    pub unit_number: String,
+    /// When the snapshot was taken, as `YYYY-MM-DD`.
+    pub taken_on: String,
+    pub ministering: Ministering,
+}
+
+impl Snapshot {
+    pub fn new(
+        organization: MinisteringOrganization,
+        unit_number: impl Into<String>,
+        taken_on: time::Date,
+        ministering: Ministering,
+    ) -> Self {
+        Self {
+            organization,
+            unit_number: unit_number.into(),
+            taken_on: taken_on.to_string(),
+            ministering,
+        }
+    }
+
+    /// # Errors
+    /// IO errors creating the file or JSON errors writing to it.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// # Errors
+    /// IO errors opening the file or JSON errors if it isn't a snapshot.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanionshipSummary {
+    pub id: Option<String>,
+    pub ministers: Vec<PersonRef>,
+    pub assignments: Vec<PersonRef>,
+}
+
+impl CompanionshipSummary {
+    fn new(companionship: &Companionship) -> Self {
+        Self {
+            id: companionship.id.clone(),
+            ministers: companionship
+                .ministers
+                .iter()
+                .map(|m| PersonRef {
+                    legacy_cmis_id: m.legacy_cmis_id,
+                    name: m.name.clone(),
+                })
+                .collect(),
+            assignments: companionship
+                .assignments()
+                .iter()
+                .map(|a| PersonRef {
+                    legacy_cmis_id: a.legacy_cmis_id,
+                    name: a.name.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A companionship present in both snapshots whose ministers or assignments changed.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanionshipChange {
+    pub before: CompanionshipSummary,
+    pub after: CompanionshipSummary,
+    pub added_ministers: Vec<PersonRef>,
+    pub removed_ministers: Vec<PersonRef>,
+    pub added_assignments: Vec<PersonRef>,
+    pub removed_assignments: Vec<PersonRef>,
+}
+
+/// A family whose set of ministers is different between snapshots.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FamilyChange {
+    pub family: PersonRef,
+    pub gained_ministers: Vec<PersonRef>,
+    pub lost_ministers: Vec<PersonRef>,
+}
+
+/// A minister who is in a different, still existing, companionship than before.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinisterMove {
+    pub minister: PersonRef,
+    pub from: CompanionshipSummary,
+    pub to: CompanionshipSummary,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryDiff {
+    pub organization: MinisteringOrganization,
+    pub from: String,
+    pub to: String,
+    pub new_companionships: Vec<CompanionshipSummary>,
+    pub dissolved_companionships: Vec<CompanionshipSummary>,
+    pub changed_companionships: Vec<CompanionshipChange>,
+    pub family_changes: Vec<FamilyChange>,
+    pub moved_ministers: Vec<MinisterMove>,
+}
+
+impl HistoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.new_companionships.is_empty()
+            && self.dissolved_companionships.is_empty()
+            && self.changed_companionships.is_empty()
+            && self.family_changes.is_empty()
+            && self.moved_ministers.is_empty()
+    }
+}
+
+/// Companionships are matched by LCR's ID when they have one, otherwise by who the ministers are.
+fn companionship_key(companionship: &Companionship) -> String {
+    match &companionship.id {
+        Some(id) => id.clone(),
+        None => companionship
+            .minister_ids()
+            .collect::<BTreeSet<_>>()
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join("+"),
+    }
+}
+
+fn companionships_by_key(ministering: &Ministering) -> BTreeMap<String, &Companionship> {
+    ministering
+        .all_companionships()
+        .map(|c| (companionship_key(c), c))
+        .collect()
+}
+
+/// Everyone in `a` who isn't in `b`, by legacy CMIS ID.
+fn people_missing_from(a: &[PersonRef], b: &[PersonRef]) -> Vec<PersonRef> {
+    a.iter()
+        .filter(|p| !b.iter().any(|q| q.legacy_cmis_id == p.legacy_cmis_id))
+        .cloned()
+        .collect()
+}
+
+/// Minister IDs by the family they're assigned to, along with everyone's names.
+fn ministers_by_family(
+    ministering: &Ministering,
+) -> (BTreeMap<u64, BTreeSet<u64>>, HashMap<u64, &str>) {
+    let mut families: BTreeMap<u64, BTreeSet<u64>> = BTreeMap::new();
+    for companionship in ministering.all_companionships() {
+        for family in companionship.assigned_ids() {
+            families
+                .entry(family)
+                .or_default()
+                .extend(companionship.minister_ids());
+        }
+    }
+    (families, ministering.names_by_id())
+}
+
+/// # Errors
+/// `InvalidInput` if the snapshots aren't of the same organization in the same unit.
+pub fn diff_snapshots(before: &Snapshot, after: &Snapshot) -> Result<HistoryDiff, Error> {
+    if before.organization != after.organization {
+        return Err(Error::InvalidInput(format!(
+            "Can't compare a {} snapshot with a {} snapshot",
+            before.organization, after.organization
+        )));
+    }
+    if before.unit_number != after.unit_number {
+        return Err(Error::InvalidInput(format!(
+            "Can't compare a snapshot of unit {} with one of unit {}",
+            before.unit_number, after.unit_number
+        )));
+    }
+
+    let old = companionships_by_key(&before.ministering);
+    let new = companionships_by_key(&after.ministering);
+
+    let new_companionships = new
+        .iter()
+        .filter(|(key, _)| !old.contains_key(*key))
+        .map(|(_, c)| CompanionshipSummary::new(c))
+        .collect();
+    let dissolved_companionships = old
+        .iter()
+        .filter(|(key, _)| !new.contains_key(*key))
+        .map(|(_, c)| CompanionshipSummary::new(c))
+        .collect();
+
+    let changed_companionships = old
+        .iter()
+        .filter_map(|(key, old)| {
+            let before = CompanionshipSummary::new(old);
+            let after = CompanionshipSummary::new(new.get(key)?);
+            let change = CompanionshipChange {
+                added_ministers: people_missing_from(&after.ministers, &before.ministers),
+                removed_ministers: people_missing_from(&before.ministers, &after.ministers),
+                added_assignments: people_missing_from(&after.assignments, &before.assignments),
+                removed_assignments: people_missing_from(&before.assignments, &after.assignments),
+                before,
+                after,
+            };
+
+            let unchanged = change.added_ministers.is_empty()
+                && change.removed_ministers.is_empty()
+                && change.added_assignments.is_empty()
+                && change.removed_assignments.is_empty();
+            (!unchanged).then_some(change)
+        })
+        .collect();
+
+    let (old_families, old_names) = ministers_by_family(&before.ministering);
+    let (new_families, new_names) = ministers_by_family(&after.ministering);
+    let person = |id: u64| PersonRef {
+        legacy_cmis_id: id,
+        name: new_names
+            .get(&id)
+            .or_else(|| old_names.get(&id))
+            .unwrap_or(&"Unknown")
+            .to_string(),
+    };
+    let empty = BTreeSet::new();
+    let family_changes = old_families
+        .keys()
+        .chain(new_families.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|family| {
+            let old = old_families.get(family).unwrap_or(&empty);
+            let new = new_families.get(family).unwrap_or(&empty);
+            if old == new {
+                return None;
+            }
+
+            Some(FamilyChange {
+                family: person(*family),
+                gained_ministers: new.difference(old).map(|id| person(*id)).collect(),
+                lost_ministers: old.difference(new).map(|id| person(*id)).collect(),
+            })
+        })
+        .collect();
+
+    let key_by_minister = |companionships: &BTreeMap<String, &Companionship>| {
+        companionships
+            .iter()
+            .flat_map(|(key, c)| c.minister_ids().map(move |id| (id, key.clone())))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let old_keys = key_by_minister(&old);
+    let new_keys = key_by_minister(&new);
+    let moved_ministers = old_keys
+        .iter()
+        .filter_map(|(minister, old_key)| {
+            let new_key = new_keys.get(minister)?;
+            if old_key == new_key || !old.contains_key(new_key) {
+                // Ministers in brand new companionships are already covered by those.
+                return None;
+            }
+
+            Some(MinisterMove {
+                minister: person(*minister),
+                from: CompanionshipSummary::new(old[old_key]),
+                to: CompanionshipSummary::new(new[new_key]),
+            })
+        })
+        .collect();
+
+    Ok(HistoryDiff {
+        organization: after.organization,
+        from: before.taken_on.clone(),
+        to: after.taken_on.clone(),
+        new_companionships,
+        dissolved_companionships,
+        changed_companionships,
+        family_changes,
+        moved_ministers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(taken_on: &str, json: &str) -> Snapshot {
+        Snapshot {
+            organization: MinisteringOrganization::EldersQuorum,
+            unit_number: "12345".to_string(),
+            taken_on: taken_on.to_string(),
+            ministering: serde_json::from_str(json).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = snapshot(
+            "2026-01-01",
+            r#"{"elders": [{"companionships": [
+                {"id": "a", "ministers": [{"name": "One", "legacyCmisId": 1}, {"name": "Two", "legacyCmisId": 2}],
+                 "assignments": [{"name": "Ten", "legacyCmisId": 10}]},
+                {"id": "b", "ministers": [{"name": "Three", "legacyCmisId": 3}],
+                 "assignments": [{"name": "Eleven", "legacyCmisId": 11}]},
+                {"id": "c", "ministers": [{"name": "Four", "legacyCmisId": 4}],
+                 "assignments": [{"name": "Twelve", "legacyCmisId": 12}]}
+            ]}]}"#,
+        );
+        let after = snapshot(
+            "2026-02-01",
+            r#"{"elders": [{"companionships": [
+                {"id": "a", "ministers": [{"name": "One", "legacyCmisId": 1}],
+                 "assignments": [{"name": "Ten", "legacyCmisId": 10}, {"name": "Twelve", "legacyCmisId": 12}]},
+                {"id": "b", "ministers": [{"name": "Three", "legacyCmisId": 3}, {"name": "Two", "legacyCmisId": 2}],
+                 "assignments": [{"name": "Eleven", "legacyCmisId": 11}]},
+                {"id": "d", "ministers": [{"name": "Five", "legacyCmisId": 5}], "assignments": null}
+            ]}]}"#,
+        );
+
+        let diff = diff_snapshots(&before, &after).unwrap();
+        assert_eq!(diff.from, "2026-01-01");
+        assert_eq!(diff.new_companionships[0].id.as_deref(), Some("d"));
+        assert_eq!(diff.dissolved_companionships[0].id.as_deref(), Some("c"));
+
+        assert_eq!(diff.changed_companionships.len(), 2);
+        assert_eq!(
+            diff.changed_companionships[0].removed_ministers[0].legacy_cmis_id,
+            2
+        );
+        assert_eq!(
+            diff.changed_companionships[0].added_assignments[0].legacy_cmis_id,
+            12
+        );
+        assert_eq!(
+            diff.changed_companionships[1].added_ministers[0].legacy_cmis_id,
+            2
+        );
+
+        let families: Vec<_> = diff
+            .family_changes
+            .iter()
+            .map(|f| {
+                (
+                    f.family.legacy_cmis_id,
+                    f.gained_ministers.len(),
+                    f.lost_ministers.len(),
+                )
+            })
+            .collect();
+        assert_eq!(families, vec![(10, 0, 1), (11, 1, 0), (12, 1, 1)]);
+
+        assert_eq!(diff.moved_ministers.len(), 1);
+        assert_eq!(diff.moved_ministers[0].minister.legacy_cmis_id, 2);
+        assert_eq!(diff.moved_ministers[0].to.id.as_deref(), Some("b"));
+
+        assert!(diff_snapshots(&after, &after).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let before = snapshot(
+            "2026-01-01",
+            r#"{"elders": [{"companionships": [{"ministers": [{"name": "One", "legacyCmisId": 1}], "assignments": null}]}]}"#,
+        );
+        let path = std::env::temp_dir().join("lcr_test_snapshot_round_trip.json");
+        before.save(&path).unwrap();
+        let after = Snapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(after.taken_on, "2026-01-01");
+        assert!(diff_snapshots(&before, &after).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_snapshots_mismatch() {
+        let json = r#"{"elders": []}"#;
+        let before = snapshot("2026-01-01", json);
+
+        let mut other_unit = snapshot("2026-02-01", json);
+        other_unit.unit_number = "67890".to_string();
+        assert!(diff_snapshots(&before, &other_unit).is_err());
+
+        let mut other_org = snapshot("2026-02-01", json);
+        other_org.organization = MinisteringOrganization::ReliefSociety;
+        assert!(diff_snapshots(&before, &other_org).is_err());
+    }
+}
@@ -0,0 +1,529 @@
+//! Proposes ministering changes after people move in and out. The plan only ever adds to or trims
+//! existing companionships, so nothing changes for families whose ministers are still around.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+use crate::data::{
+    Companionship, Household, Ministering, MinisteringOrganization, MovedInPerson, MovedOutPerson,
+    Sex,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Constraints {
+    pub max_families_per_companionship: usize,
+    pub ministers_per_companionship: usize,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self {
+            max_families_per_companionship: 5,
+            ministers_per_companionship: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Action {
+    RemoveMinister,
+    RemoveAssignment,
+    DissolveCompanionship,
+    AddMinister,
+    NewCompanionship,
+    LeaveUnpaired,
+    AddAssignment,
+    LeaveUnassigned,
+}
+
+/// Why a change is proposed. Households waiting for ministers are assigned in this order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Reason {
+    CompanionshipDissolved,
+    MovedIn,
+    NotAssigned,
+    MovedOut,
+    NotOnMemberList,
+    NoMinistersLeft,
+    NeedsMinister,
+    NobodyToPair,
+    NotInCompanionship,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CompanionshipDissolved => write!(f, "Their companionship was dissolved"),
+            Self::MovedIn => write!(f, "Moved in"),
+            Self::NotAssigned => write!(f, "Not assigned to anyone"),
+            Self::MovedOut => write!(f, "Moved out"),
+            Self::NotOnMemberList => write!(f, "No longer on the member list"),
+            Self::NoMinistersLeft => write!(f, "None of its ministers are left"),
+            Self::NeedsMinister => write!(f, "Companionship needs another minister"),
+            Self::NobodyToPair => write!(f, "Nobody left to pair with"),
+            Self::NotInCompanionship => write!(f, "Eligible but not in a companionship"),
+        }
+    }
+}
+
+/// One reviewable change. Flat so it can be written straight to CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposedChange {
+    pub action: Action,
+    pub companionship: Option<String>,
+    pub legacy_cmis_id: Option<u64>,
+    pub name: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Plan {
+    pub organization: MinisteringOrganization,
+    pub changes: Vec<ProposedChange>,
+}
+
+struct WorkingCompanionship {
+    label: String,
+    ministers: Vec<u64>,
+    households: Vec<usize>,
+}
+
+fn companionship_label(companionship: &Companionship) -> String {
+    match &companionship.id {
+        Some(id) => id.clone(),
+        None => companionship
+            .ministers
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" & "),
+    }
+}
+
+/// Whether an organization is expected to minister to a household. Elders quorum ministers to
+/// every household, Relief Society to households with an adult woman.
+fn needs_ministering(org: MinisteringOrganization, household: &Household) -> bool {
+    match org {
+        MinisteringOrganization::EldersQuorum => true,
+        MinisteringOrganization::ReliefSociety => household
+            .members()
+            .any(|m| m.age >= 18 && Sex::Female.matches(m)),
+    }
+}
+
+struct Area<'a> {
+    street: Option<String>,
+    city: Option<&'a str>,
+}
+
+impl<'a> Area<'a> {
+    fn of(household: &'a Household) -> Self {
+        let parsed = household.address.as_ref().map(|a| &a.parsed);
+        Self {
+            street: parsed
+                .and_then(|p| p.street_name.as_deref())
+                .map(str::to_lowercase),
+            city: parsed.and_then(|p| p.city.as_deref()),
+        }
+    }
+
+    /// 2 for the same street, 1 for the same city, 0 otherwise.
+    fn closeness(&self, other: &Area) -> usize {
+        if self.street.is_some() && self.street == other.street {
+            2
+        } else if self.city.is_some() && self.city == other.city {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+pub fn propose(
+    org: MinisteringOrganization,
+    ministering: &Ministering,
+    households: &[Household],
+    moved_in: &[MovedInPerson],
+    moved_out: &[MovedOutPerson],
+    constraints: Constraints,
+) -> Plan {
+    let household_of: HashMap<u64, usize> = households
+        .iter()
+        .enumerate()
+        .flat_map(|(i, h)| h.members().map(move |m| (m.legacy_cmis_id, i)))
+        .collect();
+    let areas: Vec<_> = households.iter().map(Area::of).collect();
+    let moved_in: HashSet<u64> = moved_in.iter().filter_map(|p| p.legacy_cmis_id).collect();
+    let moved_out: HashSet<u64> = moved_out.iter().filter_map(|p| p.legacy_cmis_id).collect();
+
+    let mut changes = Vec::new();
+    let mut change =
+        |action, companionship: Option<&str>, person: Option<(u64, &str)>, reason: String| {
+            changes.push(ProposedChange {
+                action,
+                companionship: companionship.map(str::to_string),
+                legacy_cmis_id: person.map(|(id, _)| id),
+                name: person.map(|(_, name)| name.to_string()),
+                reason,
+            })
+        };
+    let departed = |id: u64| {
+        if moved_out.contains(&id) {
+            Reason::MovedOut
+        } else {
+            Reason::NotOnMemberList
+        }
+    };
+
+    // Trim people who are gone from existing companionships.
+    let mut companionships = Vec::new();
+    let mut to_assign: Vec<(usize, Reason)> = Vec::new();
+    for companionship in ministering.all_companionships() {
+        let label = companionship_label(companionship);
+        let mut working = WorkingCompanionship {
+            label,
+            ministers: Vec::new(),
+            households: Vec::new(),
+        };
+
+        for minister in &companionship.ministers {
+            if household_of.contains_key(&minister.legacy_cmis_id) {
+                working.ministers.push(minister.legacy_cmis_id);
+            } else {
+                change(
+                    Action::RemoveMinister,
+                    Some(&working.label),
+                    Some((minister.legacy_cmis_id, &minister.name)),
+                    departed(minister.legacy_cmis_id).to_string(),
+                );
+            }
+        }
+
+        for assignment in companionship.assignments() {
+            match household_of.get(&assignment.legacy_cmis_id) {
+                Some(household) if !working.households.contains(household) => {
+                    working.households.push(*household)
+                }
+                Some(_) => {}
+                None => change(
+                    Action::RemoveAssignment,
+                    Some(&working.label),
+                    Some((assignment.legacy_cmis_id, &assignment.name)),
+                    departed(assignment.legacy_cmis_id).to_string(),
+                ),
+            }
+        }
+
+        if working.ministers.is_empty() {
+            change(
+                Action::DissolveCompanionship,
+                Some(&working.label),
+                None,
+                Reason::NoMinistersLeft.to_string(),
+            );
+            to_assign.extend(
+                working
+                    .households
+                    .into_iter()
+                    .map(|h| (h, Reason::CompanionshipDissolved)),
+            );
+        } else {
+            companionships.push(working);
+        }
+    }
+
+    // Everyone else who needs ministers, moved-in families first.
+    let covered: HashSet<usize> = companionships
+        .iter()
+        .flat_map(|c| c.households.iter().copied())
+        .chain(to_assign.iter().map(|(h, _)| *h))
+        .collect();
+    let mut unassigned: Vec<(usize, Reason)> = households
+        .iter()
+        .enumerate()
+        .filter(|(i, h)| !covered.contains(i) && needs_ministering(org, h))
+        .map(|(i, h)| {
+            if h.members().any(|m| moved_in.contains(&m.legacy_cmis_id)) {
+                (i, Reason::MovedIn)
+            } else {
+                (i, Reason::NotAssigned)
+            }
+        })
+        .collect();
+    unassigned.sort_by_key(|(_, reason)| *reason);
+    to_assign.extend(unassigned);
+
+    // Fill out short companionships, then pair up everyone left, neighbors together.
+    let serving: HashSet<u64> = companionships
+        .iter()
+        .flat_map(|c| c.ministers.iter().copied())
+        .collect();
+    let mut unpaired: Vec<(u64, usize, &str)> = households
+        .iter()
+        .enumerate()
+        .flat_map(|(i, h)| h.members().map(move |m| (i, m)))
+        .filter(|(_, m)| org.is_eligible_minister(m) && !serving.contains(&m.legacy_cmis_id))
+        .map(|(i, m)| (m.legacy_cmis_id, i, m.name_list_preferred_local.as_str()))
+        .collect();
+    unpaired.sort_by_key(|(_, household, _)| households[*household].street_sort_key());
+
+    for companionship in &mut companionships {
+        while companionship.ministers.len() < constraints.ministers_per_companionship
+            && !unpaired.is_empty()
+        {
+            let current = &areas[household_of[&companionship.ministers[0]]];
+            let (index, _) = unpaired
+                .iter()
+                .enumerate()
+                .max_by_key(|(i, (_, household, _))| {
+                    (current.closeness(&areas[*household]), usize::MAX - i)
+                })
+                .unwrap();
+            let (id, _, name) = unpaired.remove(index);
+            companionship.ministers.push(id);
+            change(
+                Action::AddMinister,
+                Some(&companionship.label),
+                Some((id, name)),
+                Reason::NeedsMinister.to_string(),
+            );
+        }
+    }
+
+    let group_size = constraints.ministers_per_companionship.max(1);
+    let mut new_count = 0;
+    for group in unpaired.chunks(group_size) {
+        if group.len() < group_size {
+            for (id, _, name) in group {
+                change(
+                    Action::LeaveUnpaired,
+                    None,
+                    Some((*id, name)),
+                    Reason::NobodyToPair.to_string(),
+                );
+            }
+            continue;
+        }
+
+        new_count += 1;
+        let label = format!("New companionship {}", new_count);
+        for (id, _, name) in group {
+            change(
+                Action::NewCompanionship,
+                Some(&label),
+                Some((*id, name)),
+                Reason::NotInCompanionship.to_string(),
+            );
+        }
+        companionships.push(WorkingCompanionship {
+            label,
+            ministers: group.iter().map(|(id, _, _)| *id).collect(),
+            households: Vec::new(),
+        });
+    }
+
+    // Assign families to the closest companionship with room, never to their own family.
+    for (household, reason) in to_assign {
+        let head = &households[household].head;
+        let best = companionships
+            .iter_mut()
+            .filter(|c| {
+                c.households.len() < constraints.max_families_per_companionship
+                    && !c.ministers.iter().any(|m| household_of[m] == household)
+            })
+            .max_by_key(|c| {
+                let closeness = c
+                    .ministers
+                    .iter()
+                    .map(|m| household_of[m])
+                    .chain(c.households.iter().copied())
+                    .map(|h| areas[household].closeness(&areas[h]))
+                    .max()
+                    .unwrap_or(0);
+                (closeness, usize::MAX - c.households.len())
+            });
+
+        match best {
+            Some(companionship) => {
+                companionship.households.push(household);
+                change(
+                    Action::AddAssignment,
+                    Some(&companionship.label),
+                    Some((head.legacy_cmis_id, &head.name_list_preferred_local)),
+                    reason.to_string(),
+                );
+            }
+            None => change(
+                Action::LeaveUnassigned,
+                None,
+                Some((head.legacy_cmis_id, &head.name_list_preferred_local)),
+                format!(
+                    "{}, but every companionship outside the household is full",
+                    reason
+                ),
+            ),
+        }
+    }
+
+    Plan {
+        organization: org,
+        changes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MemberListPerson;
+
+    fn person(id: u64, sex: &str, street: &str) -> MemberListPerson {
+        let mut person = MemberListPerson::test(id, 40, sex, &id.to_string());
+        person.address = serde_json::from_str(&format!(
+            r#"{{"addressLines": ["{}", "Provo, UT 84604"]}}"#,
+            street
+        ))
+        .unwrap();
+        person
+    }
+
+    #[test]
+    fn test_propose() {
+        let ministering: Ministering = serde_json::from_str(
+            r#"{"elders": [{"companionships": [
+                {"id": "a", "ministers": [{"name": "One", "legacyCmisId": 1}, {"name": "Two", "legacyCmisId": 2}],
+                 "assignments": [{"name": "Ten", "legacyCmisId": 10}, {"name": "Eleven", "legacyCmisId": 11}]},
+                {"id": "b", "ministers": [{"name": "Three", "legacyCmisId": 3}],
+                 "assignments": [{"name": "Twelve", "legacyCmisId": 12}]}
+            ]}]}"#,
+        )
+        .unwrap();
+        let households = Household::group(
+            vec![
+                person(1, "M", "1 Elm St"),
+                person(5, "M", "3 Elm St"),
+                person(6, "M", "7 Oak St"),
+                person(7, "M", "8 Oak St"),
+                person(10, "F", "5 Elm St"),
+                person(12, "F", "9 Oak St"),
+                person(20, "F", "2 Oak St"),
+            ],
+            1,
+        );
+        let moved_in = vec![MovedInPerson {
+            name: "Newcomer, Family".to_string(),
+            legacy_cmis_id: Some(20),
+            move_date: "20260101".to_string(),
+            prior_unit_name: None,
+        }];
+        let moved_out = vec![MovedOutPerson {
+            name: "Former, Elder".to_string(),
+            legacy_cmis_id: Some(2),
+            move_date_display: "1 Jan 2026".to_string(),
+            next_unit_name: None,
+        }];
+
+        let plan = propose(
+            MinisteringOrganization::EldersQuorum,
+            &ministering,
+            &households,
+            &moved_in,
+            &moved_out,
+            Constraints {
+                max_families_per_companionship: 2,
+                ministers_per_companionship: 2,
+            },
+        );
+
+        let changes: Vec<_> = plan
+            .changes
+            .iter()
+            .map(|c| {
+                (
+                    c.action,
+                    c.companionship.as_deref(),
+                    c.legacy_cmis_id,
+                    c.reason.as_str(),
+                )
+            })
+            .collect();
+        let new = Some("New companionship 1");
+        assert_eq!(
+            changes,
+            vec![
+                (Action::RemoveMinister, Some("a"), Some(2), "Moved out"),
+                (
+                    Action::RemoveAssignment,
+                    Some("a"),
+                    Some(11),
+                    "No longer on the member list"
+                ),
+                (
+                    Action::RemoveMinister,
+                    Some("b"),
+                    Some(3),
+                    "No longer on the member list"
+                ),
+                (
+                    Action::DissolveCompanionship,
+                    Some("b"),
+                    None,
+                    "None of its ministers are left"
+                ),
+                (
+                    Action::AddMinister,
+                    Some("a"),
+                    Some(5),
+                    "Companionship needs another minister"
+                ),
+                (
+                    Action::NewCompanionship,
+                    new,
+                    Some(6),
+                    "Eligible but not in a companionship"
+                ),
+                (
+                    Action::NewCompanionship,
+                    new,
+                    Some(7),
+                    "Eligible but not in a companionship"
+                ),
+                (
+                    Action::AddAssignment,
+                    new,
+                    Some(12),
+                    "Their companionship was dissolved"
+                ),
+                (Action::AddAssignment, new, Some(20), "Moved in"),
+                (
+                    Action::LeaveUnassigned,
+                    None,
+                    Some(1),
+                    "Not assigned to anyone, but every companionship outside the household is full"
+                ),
+                (
+                    Action::LeaveUnassigned,
+                    None,
+                    Some(5),
+                    "Not assigned to anyone, but every companionship outside the household is full"
+                ),
+                (
+                    Action::AddAssignment,
+                    Some("a"),
+                    Some(6),
+                    "Not assigned to anyone"
+                ),
+                (
+                    Action::LeaveUnassigned,
+                    None,
+                    Some(7),
+                    "Not assigned to anyone, but every companionship outside the household is full"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+//! Comparing people's names the way they're typed, regardless of case and accents.
+
+/// Lowercases, strips accents (`José` becomes `jose`) and splits on anything that isn't a letter
+/// or digit.
+pub fn normalize(s: &str) -> Vec<String> {
+    deunicode::deunicode(s)
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
@@ -0,0 +1,148 @@
+//! Ordinance summaries built from `Client::member_profiles`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::data::{MemberListPerson, MemberProfile, OrdinanceType, PersonRef, Sex};
+
+// Lower bounds of the age groups unendowed adults are counted in.
+const AGE_GROUPS: [u8; 7] = [18, 30, 40, 50, 60, 70, 80];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrdinanceSummary {
+    pub adults: usize,
+    /// How many adults have received each ordinance.
+    pub ordinance_counts: BTreeMap<OrdinanceType, usize>,
+    /// Unendowed adults, keyed by the lower bound of their age group.
+    pub unendowed_by_age_group: BTreeMap<u8, usize>,
+    /// Young men and men old enough to hold the priesthood who haven't been ordained.
+    pub unordained: Vec<PersonRef>,
+    /// Men who hold the Aaronic but not the Melchizedek Priesthood.
+    pub without_melchizedek_priesthood: Vec<PersonRef>,
+}
+
+fn age_group(age: u8) -> u8 {
+    AGE_GROUPS
+        .iter()
+        .copied()
+        .rfind(|lower| *lower <= age)
+        .unwrap_or(AGE_GROUPS[0])
+}
+
+fn person_ref(member: &MemberListPerson) -> PersonRef {
+    PersonRef {
+        legacy_cmis_id: member.legacy_cmis_id,
+        name: member.name_list_preferred_local.clone(),
+    }
+}
+
+/// Members without a fetched profile are left out.
+pub fn summarize(members: &[MemberListPerson], profiles: &[MemberProfile]) -> OrdinanceSummary {
+    let profiles_by_id: HashMap<u64, &MemberProfile> =
+        profiles.iter().map(|p| (p.individual.id, p)).collect();
+    let with_profiles: Vec<_> = members
+        .iter()
+        .filter_map(|m| Some((m, *profiles_by_id.get(&m.legacy_cmis_id)?)))
+        .collect();
+
+    let mut summary = OrdinanceSummary {
+        adults: 0,
+        ordinance_counts: BTreeMap::new(),
+        unendowed_by_age_group: BTreeMap::new(),
+        unordained: Vec::new(),
+        without_melchizedek_priesthood: Vec::new(),
+    };
+
+    for (member, profile) in &with_profiles {
+        let is_male = Sex::Male.matches(member);
+        if is_male && member.age >= 12 {
+            if !profile.has_ordinance(OrdinanceType::AaronicPriesthood)
+                && !profile.has_ordinance(OrdinanceType::MelchizedekPriesthood)
+            {
+                summary.unordained.push(person_ref(member));
+            } else if member.age >= 18
+                && !profile.has_ordinance(OrdinanceType::MelchizedekPriesthood)
+            {
+                summary
+                    .without_melchizedek_priesthood
+                    .push(person_ref(member));
+            }
+        }
+
+        if member.age < 18 {
+            continue;
+        }
+        summary.adults += 1;
+
+        let mut received: Vec<_> = profile
+            .ordinances
+            .iter()
+            .map(|o| o.ordinance_type)
+            .collect();
+        if profile.is_endowed() && !received.contains(&OrdinanceType::Endowment) {
+            received.push(OrdinanceType::Endowment);
+        }
+        received.sort();
+        received.dedup();
+        for ordinance_type in received {
+            *summary.ordinance_counts.entry(ordinance_type).or_default() += 1;
+        }
+
+        if !profile.is_endowed() {
+            *summary
+                .unendowed_by_age_group
+                .entry(age_group(member.age))
+                .or_default() += 1;
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(id: u64, ordinances: &[&str]) -> MemberProfile {
+        let ordinances: Vec<_> = ordinances
+            .iter()
+            .map(|o| format!(r#"{{"type": "{}"}}"#, o))
+            .collect();
+        serde_json::from_str(&format!(
+            r#"{{"individual": {{"id": {}}}, "ordinances": [{}]}}"#,
+            id,
+            ordinances.join(",")
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_summarize() {
+        let members = vec![
+            MemberListPerson::test(1, 45, "M", "a"),
+            MemberListPerson::test(2, 19, "M", "b"),
+            MemberListPerson::test(3, 14, "M", "c"),
+            MemberListPerson::test(4, 33, "F", "d"),
+            MemberListPerson::test(5, 70, "F", "e"),
+        ];
+        let profiles = vec![
+            profile(1, &["BAPTISM", "MELCHIZEDEK_PRIESTHOOD", "ENDOWMENT"]),
+            profile(2, &["BAPTISM", "AARONIC_PRIESTHOOD"]),
+            profile(3, &["BAPTISM"]),
+            profile(4, &["BAPTISM", "SEALED_TO_PARENTS"]),
+        ];
+
+        let summary = summarize(&members, &profiles);
+        assert_eq!(summary.adults, 3);
+        assert_eq!(summary.ordinance_counts[&OrdinanceType::Baptism], 3);
+        assert_eq!(summary.ordinance_counts[&OrdinanceType::Endowment], 1);
+        assert_eq!(
+            summary.unendowed_by_age_group,
+            BTreeMap::from([(18, 1), (30, 1)])
+        );
+        assert_eq!(summary.unordained[0].legacy_cmis_id, 3);
+        assert_eq!(summary.without_melchizedek_priesthood[0].legacy_cmis_id, 2);
+    }
+}
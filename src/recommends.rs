@@ -0,0 +1,101 @@
+//! Upcoming temple recommend expirations, built from `Client::recommend_status`.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::Date;
+
+use crate::data::{RecommendStatus, RecommendType};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringRecommend<'a> {
+    /// Year and month of the expiration, like `2025-07`.
+    pub month: String,
+    pub name: &'a str,
+    pub legacy_cmis_id: u64,
+    pub recommend_type: Option<RecommendType>,
+    pub expiration_date: String,
+    pub expired: bool,
+}
+
+fn month_index(date: Date) -> i32 {
+    date.year() * 12 + i32::from(u8::from(date.month())) - 1
+}
+
+/// Recommends that have expired or expire by the end of the month `within_months` from now,
+/// soonest first. People without an expiration date, who don't hold a recommend, are left out.
+pub fn expiring(
+    statuses: &[RecommendStatus],
+    today: Date,
+    within_months: u8,
+) -> Vec<ExpiringRecommend<'_>> {
+    let last_month = month_index(today) + i32::from(within_months);
+    let mut expiring: Vec<_> = statuses
+        .iter()
+        .filter_map(|s| Some((s, s.expiration_date()?)))
+        .filter(|(_, date)| month_index(*date) <= last_month)
+        .collect();
+    expiring.sort_by_key(|(_, date)| *date);
+
+    expiring
+        .into_iter()
+        .map(|(status, date)| ExpiringRecommend {
+            month: format!("{}-{:02}", date.year(), u8::from(date.month())),
+            name: &status.name,
+            legacy_cmis_id: status.legacy_cmis_id,
+            recommend_type: status.recommend_type,
+            expiration_date: date.to_string(),
+            expired: date < today,
+        })
+        .collect()
+}
+
+pub fn group_by_month<'a, 'b>(
+    expiring: &'b [ExpiringRecommend<'a>],
+) -> BTreeMap<&'b str, Vec<&'b ExpiringRecommend<'a>>> {
+    let mut by_month: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for recommend in expiring {
+        by_month
+            .entry(recommend.month.as_str())
+            .or_default()
+            .push(recommend);
+    }
+    by_month
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    fn status(id: u64, expiration_date: Option<&str>) -> RecommendStatus {
+        RecommendStatus {
+            name: format!("Person {}", id),
+            legacy_cmis_id: id,
+            recommend_type: Some(RecommendType::Standard),
+            expiration_date: expiration_date.map(|d| d.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_expiring() {
+        let statuses = vec![
+            status(1, Some("20250831")),
+            status(2, Some("20250501")),
+            status(3, None),
+            status(4, Some("20250901")),
+            status(5, Some("20250715")),
+        ];
+
+        let expiring = expiring(&statuses, date!(2025 - 06 - 10), 2);
+        let ids: Vec<_> = expiring.iter().map(|r| r.legacy_cmis_id).collect();
+        assert_eq!(ids, vec![2, 5, 1]);
+        assert!(expiring[0].expired);
+        assert!(!expiring[1].expired);
+
+        let by_month = group_by_month(&expiring);
+        let months: Vec<_> = by_month.keys().copied().collect();
+        assert_eq!(months, vec!["2025-05", "2025-07", "2025-08"]);
+    }
+}
@@ -0,0 +1,109 @@
+//! Trends in weekly sacrament meeting attendance, built from `Client::sacrament_attendance`.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use time::{Date, Duration, Weekday};
+
+use crate::data::SacramentAttendanceWeek;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendWeek {
+    pub date: String,
+    pub count: u32,
+    /// Average of this week and the weeks before it, up to the window size.
+    pub rolling_average: f32,
+    /// The count on the same Sunday a year earlier, if one was entered.
+    pub last_year: Option<u32>,
+    pub change_from_last_year: Option<i64>,
+}
+
+/// `today` if it's a Sunday, otherwise the Sunday before it.
+pub fn most_recent_sunday(today: Date) -> Date {
+    if today.weekday() == Weekday::Sunday {
+        today
+    } else {
+        today.prev_occurrence(Weekday::Sunday)
+    }
+}
+
+/// One entry per week with a count, oldest first. Weeks without a count are skipped rather than
+/// dragging the averages down.
+pub fn trend(weeks: &[SacramentAttendanceWeek], window: usize) -> Vec<TrendWeek> {
+    let counts: BTreeMap<Date, u32> = weeks
+        .iter()
+        .filter_map(|w| Some((w.date()?, w.count?)))
+        .collect();
+    let ordered: Vec<_> = counts.iter().map(|(d, c)| (*d, *c)).collect();
+    let window = window.max(1);
+
+    ordered
+        .iter()
+        .enumerate()
+        .map(|(i, (date, count))| {
+            let recent = &ordered[(i + 1).saturating_sub(window)..=i];
+            let rolling_average =
+                recent.iter().map(|(_, c)| *c as f32).sum::<f32>() / recent.len() as f32;
+            // 52 weeks back lands on a Sunday too, unlike a calendar year.
+            let last_year = counts.get(&(*date - Duration::weeks(52))).copied();
+
+            TrendWeek {
+                date: date.to_string(),
+                count: *count,
+                rolling_average,
+                last_year,
+                change_from_last_year: last_year.map(|l| i64::from(*count) - i64::from(l)),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn week(date: &str, count: Option<u32>) -> SacramentAttendanceWeek {
+        SacramentAttendanceWeek {
+            date: date.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_most_recent_sunday() {
+        use time::macros::date;
+
+        assert_eq!(
+            most_recent_sunday(date!(2025 - 06 - 08)),
+            date!(2025 - 06 - 08)
+        );
+        assert_eq!(
+            most_recent_sunday(date!(2025 - 06 - 14)),
+            date!(2025 - 06 - 08)
+        );
+    }
+
+    #[test]
+    fn test_trend() {
+        let weeks = vec![
+            week("2024-06-09", Some(100)),
+            week("2025-06-01", Some(120)),
+            week("2025-06-08", Some(130)),
+            week("2025-06-15", None),
+            week("2025-05-25", Some(110)),
+        ];
+
+        let trend = trend(&weeks, 2);
+        let dates: Vec<_> = trend.iter().map(|w| w.date.as_str()).collect();
+        assert_eq!(
+            dates,
+            vec!["2024-06-09", "2025-05-25", "2025-06-01", "2025-06-08"]
+        );
+        assert_eq!(trend[0].rolling_average, 100.0);
+        assert_eq!(trend[3].rolling_average, 125.0);
+        assert_eq!(trend[3].last_year, Some(100));
+        assert_eq!(trend[3].change_from_last_year, Some(30));
+        assert_eq!(trend[2].last_year, None);
+    }
+}
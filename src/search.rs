@@ -0,0 +1,116 @@
+//! Fuzzy, accent-insensitive search over member names.
+
+use serde::Serialize;
+
+use crate::data::MemberListPerson;
+use crate::names::normalize;
+
+// How similar a query word has to be to a name word to count, from 0 to 1.
+const MIN_SIMILARITY: f64 = 0.85;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch<'a> {
+    pub member: &'a MemberListPerson,
+    /// 1 for an exact or prefix match of every word, lower for misspellings.
+    pub score: f64,
+}
+
+fn word_score(query_word: &str, name_words: &[String]) -> f64 {
+    name_words
+        .iter()
+        .map(|w| {
+            if w.starts_with(query_word) {
+                1.0
+            } else {
+                strsim::jaro_winkler(query_word, w)
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Members whose names match every word of `query`, best matches first. Words match a name word
+/// they're a prefix of, or one that's spelled similarly.
+pub fn search<'a>(members: &'a [MemberListPerson], query: &str) -> Vec<SearchMatch<'a>> {
+    let query_words = normalize(query);
+    if query_words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<_> = members
+        .iter()
+        .filter_map(|member| {
+            let name_words: Vec<_> = [
+                &member.name_list_preferred_local,
+                &member.name_given_preferred_local,
+                &member.name_family_preferred_local,
+            ]
+            .iter()
+            .flat_map(|n| normalize(n))
+            .collect();
+
+            let scores: Vec<_> = query_words
+                .iter()
+                .map(|q| word_score(q, &name_words))
+                .collect();
+            if scores.iter().any(|s| *s < MIN_SIMILARITY) {
+                return None;
+            }
+
+            Some(SearchMatch {
+                member,
+                score: scores.iter().sum::<f64>() / scores.len() as f64,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score.total_cmp(&a.score).then_with(|| {
+            a.member
+                .name_list_preferred_local
+                .cmp(&b.member.name_list_preferred_local)
+        })
+    });
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(id: u64, given: &str, family: &str) -> MemberListPerson {
+        let mut member = MemberListPerson::test(id, 30, "F", "a");
+        member.name_given_preferred_local = given.to_string();
+        member.name_family_preferred_local = family.to_string();
+        member.name_list_preferred_local = format!("{}, {}", family, given);
+        member
+    }
+
+    #[test]
+    fn test_search() {
+        let members = vec![
+            member(1, "José", "Núñez"),
+            member(2, "Joseph", "Smith"),
+            member(3, "Emma", "Smith"),
+            member(4, "Jane", "Doe"),
+        ];
+
+        let ids = |query| -> Vec<u64> {
+            search(&members, query)
+                .iter()
+                .map(|m| m.member.legacy_cmis_id)
+                .collect()
+        };
+        assert_eq!(ids("jose nunez"), vec![1]);
+        assert_eq!(ids("Núñez"), vec![1]);
+        assert_eq!(ids("smith"), vec![3, 2]);
+        assert_eq!(ids("smith, em"), vec![3]);
+        // Misspelled, so a weaker match than the exact prefix.
+        assert_eq!(ids("smiht"), vec![3, 2]);
+        assert!(search(&members, "smiht")[0].score < 1.0);
+        assert_eq!(search(&members, "smith")[0].score, 1.0);
+        assert_eq!(ids("jo"), vec![1, 2]);
+        assert!(ids("xyz").is_empty());
+        assert!(ids("  ").is_empty());
+    }
+}
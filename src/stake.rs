@@ -0,0 +1,239 @@
+//! Combining the same data from several units, for stake-level reports. Fetch each unit's data
+//! with `Client::per_unit`, then combine it here.
+
+use serde::Serialize;
+
+use crate::data::{MemberListPerson, MovedInPerson, MovedOutPerson, Sex};
+
+/// A unit's number and name, as it should appear in combined output.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitLabel {
+    pub unit_number: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitResults<T> {
+    pub unit: UnitLabel,
+    pub subtotal: usize,
+    pub items: Vec<T>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeResults<T> {
+    pub units: Vec<UnitResults<T>>,
+    pub total: usize,
+}
+
+impl<T> StakeResults<T> {
+    pub fn new(per_unit: Vec<(UnitLabel, Vec<T>)>) -> Self {
+        let units: Vec<_> = per_unit
+            .into_iter()
+            .map(|(unit, items)| UnitResults {
+                unit,
+                subtotal: items.len(),
+                items,
+            })
+            .collect();
+        let total = units.iter().map(|u| u.subtotal).sum();
+        Self { units, total }
+    }
+
+    /// Every item alongside the name of its unit.
+    pub fn iter(&self) -> impl Iterator<Item = (&UnitLabel, &T)> {
+        self.units
+            .iter()
+            .flat_map(|u| u.items.iter().map(move |i| (&u.unit, i)))
+    }
+}
+
+/// One member per row with a unit column, for flat exports like CSV.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeMemberRow<'a> {
+    pub unit: &'a str,
+    pub name: &'a str,
+    pub legacy_cmis_id: u64,
+    pub age: u8,
+    pub sex: &'a str,
+}
+
+pub fn member_rows(members: &StakeResults<MemberListPerson>) -> Vec<StakeMemberRow<'_>> {
+    members
+        .iter()
+        .map(|(unit, m)| StakeMemberRow {
+            unit: &unit.name,
+            name: &m.name_list_preferred_local,
+            legacy_cmis_id: m.legacy_cmis_id,
+            age: m.age,
+            sex: &m.sex,
+        })
+        .collect()
+}
+
+/// One move per row with a unit column. `otherUnit` is where they moved from for move-ins and
+/// to for move-outs.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakeMoveRow<'a> {
+    pub unit: &'a str,
+    pub direction: &'static str,
+    pub name: &'a str,
+    pub date: &'a str,
+    pub other_unit: Option<&'a str>,
+}
+
+pub fn move_rows<'a>(
+    moved_in: &'a StakeResults<MovedInPerson>,
+    moved_out: &'a StakeResults<MovedOutPerson>,
+) -> Vec<StakeMoveRow<'a>> {
+    moved_in
+        .iter()
+        .map(|(unit, p)| StakeMoveRow {
+            unit: &unit.name,
+            direction: "in",
+            name: &p.name,
+            date: &p.move_date,
+            other_unit: p.prior_unit_name.as_deref(),
+        })
+        .chain(moved_out.iter().map(|(unit, p)| StakeMoveRow {
+            unit: &unit.name,
+            direction: "out",
+            name: &p.name,
+            date: &p.move_date_display,
+            other_unit: p.next_unit_name.as_deref(),
+        }))
+        .collect()
+}
+
+/// Counts for one unit, or for the whole stake in the last row of [`summarize`].
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnitSummary {
+    pub unit: String,
+    pub members: usize,
+    pub adults: usize,
+    pub youth: usize,
+    pub children: usize,
+    pub males: usize,
+    pub females: usize,
+    pub moved_in: usize,
+    pub moved_out: usize,
+}
+
+/// A row per unit followed by a `Total` row. The three results must have the same units in the
+/// same order, as they do when fetched together.
+pub fn summarize(
+    members: &StakeResults<MemberListPerson>,
+    moved_in: &StakeResults<MovedInPerson>,
+    moved_out: &StakeResults<MovedOutPerson>,
+) -> Vec<UnitSummary> {
+    let mut rows: Vec<_> = members
+        .units
+        .iter()
+        .zip(&moved_in.units)
+        .zip(&moved_out.units)
+        .map(|((members, moved_in), moved_out)| {
+            let count = |f: &dyn Fn(&MemberListPerson) -> bool| {
+                members.items.iter().filter(|m| f(m)).count()
+            };
+            UnitSummary {
+                unit: members.unit.name.clone(),
+                members: members.subtotal,
+                adults: count(&|m| m.age >= 18),
+                youth: count(&|m| (12..18).contains(&m.age)),
+                children: count(&|m| m.age < 12),
+                males: count(&|m| Sex::Male.matches(m)),
+                females: count(&|m| Sex::Female.matches(m)),
+                moved_in: moved_in.subtotal,
+                moved_out: moved_out.subtotal,
+            }
+        })
+        .collect();
+
+    let total = rows.iter().fold(
+        UnitSummary {
+            unit: "Total".to_string(),
+            ..Default::default()
+        },
+        |mut total, row| {
+            total.members += row.members;
+            total.adults += row.adults;
+            total.youth += row.youth;
+            total.children += row.children;
+            total.males += row.males;
+            total.females += row.females;
+            total.moved_in += row.moved_in;
+            total.moved_out += row.moved_out;
+            total
+        },
+    );
+    rows.push(total);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(number: &str) -> UnitLabel {
+        UnitLabel {
+            unit_number: number.to_string(),
+            name: format!("Ward {}", number),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        let members = StakeResults::new(vec![
+            (
+                label("1"),
+                vec![
+                    MemberListPerson::test(1, 40, "M", "a"),
+                    MemberListPerson::test(2, 14, "F", "a"),
+                ],
+            ),
+            (label("2"), vec![MemberListPerson::test(3, 5, "F", "b")]),
+        ]);
+        let moved_in = StakeResults::new(vec![
+            (label("1"), Vec::new()),
+            (
+                label("2"),
+                vec![MovedInPerson {
+                    name: "New".to_string(),
+                    legacy_cmis_id: None,
+                    move_date: "2025-06-01".to_string(),
+                    prior_unit_name: None,
+                }],
+            ),
+        ]);
+        let moved_out = StakeResults::new(vec![(label("1"), Vec::new()), (label("2"), Vec::new())]);
+
+        assert_eq!(members.total, 3);
+        assert_eq!(members.units[0].subtotal, 2);
+        assert_eq!(member_rows(&members)[2].unit, "Ward 2");
+        assert_eq!(move_rows(&moved_in, &moved_out)[0].direction, "in");
+
+        let summary = summarize(&members, &moved_in, &moved_out);
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[0].adults, 1);
+        assert_eq!(summary[0].youth, 1);
+        assert_eq!(
+            summary[2],
+            UnitSummary {
+                unit: "Total".to_string(),
+                members: 3,
+                adults: 1,
+                youth: 1,
+                children: 1,
+                males: 1,
+                females: 2,
+                moved_in: 1,
+                moved_out: 0,
+            }
+        );
+    }
+}